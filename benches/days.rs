@@ -0,0 +1,92 @@
+//! Parse, part one and part two of every day, timed separately on the real
+//! input and on the examples from the tests.
+//!
+//! `cargo bench -- --save-baseline <name>` records a baseline and
+//! `cargo bench -- --baseline <name>` compares against it.
+
+use criterion::measurement::WallTime;
+use criterion::{black_box, criterion_group, criterion_main, BenchmarkGroup, Criterion};
+
+use aoc2020::Part;
+use aoc2020::days::*;
+use aoc2020::input::{self, Source};
+use aoc2020::solver::{self, Solver};
+
+const BOTH: &[Part] = &[Part::One, Part::Two];
+const ONE: &[Part] = &[Part::One];
+const TWO: &[Part] = &[Part::Two];
+/// Parsing only, for examples the parts cannot solve as they are.
+const NONE: &[Part] = &[];
+
+/// Benchmarks parsing `input` and solving `parts` of it.
+fn bench_input<S: Solver>(group: &mut BenchmarkGroup<WallTime>, label: &str, input: &str, parts: &[Part]) {
+    let parsed = match solver::parse::<S>(input) {
+        Ok(parsed) => parsed,
+        Err(err) => {
+            eprintln!("skipping day {} {}: {}", S::DAY, label, err);
+            return;
+        }
+    };
+
+    group.bench_function(format!("{}/parse", label), |b| b.iter(|| S::parse(black_box(input))));
+
+    for &part in parts {
+        match part {
+            Part::One => group.bench_function(format!("{}/part_one", label), |b| b.iter(|| S::part_one(black_box(&parsed)))),
+            Part::Two => group.bench_function(format!("{}/part_two", label), |b| b.iter(|| S::part_two(black_box(&parsed))))
+        };
+    }
+}
+
+/// Benchmarks a day on its real input, if there is one, and on `examples`.
+fn bench_day<S: Solver>(c: &mut Criterion, examples: &[(&str, &str, &[Part])]) {
+    let mut group = c.benchmark_group(format!("day{:02}", S::DAY));
+    group.sample_size(10);
+
+    match input::load(S::DAY, &Source::Default) {
+        Ok(input) => bench_input::<S>(&mut group, "input", &input, BOTH),
+        Err(err) => eprintln!("skipping day {} input: {}", S::DAY, err)
+    }
+
+    for &(label, example, parts) in examples {
+        bench_input::<S>(&mut group, label, example, parts);
+    }
+
+    group.finish();
+}
+
+fn days(c: &mut Criterion) {
+    bench_day::<day1::Day1>(c, &[("example", day1::EXAMPLE, BOTH)]);
+    bench_day::<day2::Day2>(c, &[("example", day2::EXAMPLE, BOTH)]);
+    bench_day::<day3::Day3>(c, &[("example", day3::EXAMPLE, BOTH)]);
+    bench_day::<day4::Day4>(c, &[
+        ("example_1", day4::EXAMPLE_1, BOTH),
+        ("example_2", day4::EXAMPLE_2, TWO),
+        ("example_3", day4::EXAMPLE_3, TWO)
+    ]);
+    bench_day::<day5::Day5>(c, &[("example", day5::EXAMPLE, BOTH)]);
+    bench_day::<day6::Day6>(c, &[("example", day6::EXAMPLE, BOTH)]);
+    bench_day::<day7::Day7>(c, &[("example_1", day7::EXAMPLE_1, BOTH), ("example_2", day7::EXAMPLE_2, TWO)]);
+    bench_day::<day8::Day8>(c, &[("example", day8::EXAMPLE, BOTH)]);
+    // The example has a preamble of 5, the solver expects 25.
+    bench_day::<day9::Day9>(c, &[("example", day9::EXAMPLE, NONE)]);
+    bench_day::<day10::Day10>(c, &[("example", day10::EXAMPLE, BOTH)]);
+    bench_day::<day11::Day11>(c, &[("example", day11::EXAMPLE, BOTH)]);
+    bench_day::<day12::Day12>(c, &[("example", day12::EXAMPLE, BOTH)]);
+    bench_day::<day13::Day13>(c, &[("example", day13::EXAMPLE, BOTH)]);
+    bench_day::<day14::Day14>(c, &[("example_1", day14::EXAMPLE_1, ONE), ("example_2", day14::EXAMPLE_2, TWO)]);
+    bench_day::<day15::Day15>(c, &[("example", day15::EXAMPLE, BOTH)]);
+    bench_day::<day16::Day16>(c, &[("example_1", day16::EXAMPLE_1, ONE), ("example_2", day16::EXAMPLE_2, TWO)]);
+    bench_day::<day17::Day17>(c, &[("example", day17::EXAMPLE, BOTH)]);
+    bench_day::<day18::Day18>(c, &[("example", day18::EXAMPLE, BOTH)]);
+    bench_day::<day19::Day19>(c, &[("example_1", day19::EXAMPLE_1, ONE), ("example_2", day19::EXAMPLE_2, BOTH)]);
+    bench_day::<day20::Day20>(c, &[("example", day20::EXAMPLE, BOTH)]);
+    bench_day::<day21::Day21>(c, &[("example", day21::EXAMPLE, BOTH)]);
+    bench_day::<day22::Day22>(c, &[("example", day22::EXAMPLE, BOTH)]);
+    bench_day::<day23::Day23>(c, &[("example", day23::EXAMPLE, BOTH)]);
+    bench_day::<day24::Day24>(c, &[("example", day24::EXAMPLE, BOTH)]);
+    bench_day::<day25::Day25>(c, &[("example", day25::EXAMPLE, ONE)]);
+}
+
+criterion_group!(benches, days);
+criterion_main!(benches);
@@ -0,0 +1,163 @@
+use std::collections::HashMap;
+use std::fmt;
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+
+use toml::Value;
+
+use crate::Part;
+use crate::days::{self, Options};
+use crate::error::ParseError;
+
+pub const ANSWERS_FILE: &str = "answers.toml";
+
+#[derive(Debug)]
+pub enum AnswersError {
+    Io { path: PathBuf, err: io::Error },
+    Toml(toml::de::Error),
+    Invalid { key: String, reason: &'static str }
+}
+
+impl fmt::Display for AnswersError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            AnswersError::Io { path, err } => write!(f, "cannot read {}: {}", path.display(), err),
+            AnswersError::Toml(err) => write!(f, "malformed answers: {}", err),
+            AnswersError::Invalid { key, reason } => write!(f, "malformed answers at `{}`: {}", key, reason)
+        }
+    }
+}
+
+impl std::error::Error for AnswersError {}
+
+/// Expected answers, one `[dayN]` table with `part1` and `part2` per day.
+///
+/// ```toml
+/// [day21]
+/// part1 = 2542
+/// part2 = "hkflr,ctmcqjf,bfrq,srxphcm,snmxl,zvx,bd,mqvk"
+/// ```
+#[derive(Debug, Default)]
+pub struct Answers {
+    expected: HashMap<(usize, usize), String>
+}
+
+impl Answers {
+    pub fn parse(text: &str) -> Result<Self, AnswersError> {
+        let root = text.parse::<Value>().map_err(AnswersError::Toml)?;
+        let invalid = |key: &str, reason| AnswersError::Invalid { key: key.to_string(), reason };
+
+        let mut expected = HashMap::new();
+
+        for (day_key, parts) in root.as_table().into_iter().flatten() {
+            let day = day_key.strip_prefix("day")
+                .and_then(|d| d.parse::<usize>().ok())
+                .filter(|d| (1..=days::DAYS).contains(d))
+                .ok_or_else(|| invalid(day_key, "expected a table named day1 to day25"))?;
+
+            let parts = parts.as_table().ok_or_else(|| invalid(day_key, "expected a table"))?;
+
+            for (part_key, answer) in parts {
+                let key = format!("{}.{}", day_key, part_key);
+
+                let part = match part_key.as_str() {
+                    "part1" => 1,
+                    "part2" => 2,
+                    _ => { return Err(invalid(&key, "expected part1 or part2")); }
+                };
+
+                let answer = match answer {
+                    Value::Integer(v) => v.to_string(),
+                    Value::String(v) => v.clone(),
+                    _ => { return Err(invalid(&key, "expected an integer or a string")); }
+                };
+
+                expected.insert((day, part), answer);
+            }
+        }
+
+        Ok(Answers { expected })
+    }
+
+    pub fn load(path: &Path) -> Result<Self, AnswersError> {
+        let text = fs::read_to_string(path).map_err(|err| AnswersError::Io { path: path.to_path_buf(), err })?;
+        Answers::parse(&text)
+    }
+
+    /// The answers shipped next to the crate manifest.
+    pub fn bundled() -> Result<Self, AnswersError> {
+        Answers::load(&Path::new(env!("CARGO_MANIFEST_DIR")).join(ANSWERS_FILE))
+    }
+
+    pub fn get(&self, day: usize, part: Part) -> Option<&str> {
+        self.expected.get(&(day, part.number())).map(String::as_str)
+    }
+
+    /// Solves `part` of `day` on `input` with `options` and compares it with
+    /// the expected answer.
+    pub fn check(&self, day: usize, part: Part, input: &str, options: &Options) -> Result<Verdict, ParseError> {
+        let expected = self.get(day, part).map(str::to_string);
+        let actual = days::solve_with(day, part, input, options)?.map(|answer| answer.to_string());
+
+        if expected == actual {
+            Ok(Verdict::Correct(actual))
+        } else {
+            Ok(Verdict::Wrong { expected, actual })
+        }
+    }
+}
+
+/// Outcome of checking one part, `None` standing for no answer at all.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum Verdict {
+    Correct(Option<String>),
+    Wrong { expected: Option<String>, actual: Option<String> }
+}
+
+impl fmt::Display for Verdict {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let show = |answer: &Option<String>| answer.clone().unwrap_or_else(|| "nothing".to_string());
+
+        match self {
+            Verdict::Correct(answer) => write!(f, "ok, {}", show(answer)),
+            Verdict::Wrong { expected, actual } => write!(f, "WRONG, expected {}, got {}", show(expected), show(actual))
+        }
+    }
+}
+
+#[test]
+fn malformed() {
+    assert!(matches!(Answers::parse("[day26]\npart1 = 1"), Err(AnswersError::Invalid { .. })));
+    assert!(matches!(Answers::parse("[day1]\npart3 = 1"), Err(AnswersError::Invalid { .. })));
+    assert!(matches!(Answers::parse("[day1]\npart1 = 1.5"), Err(AnswersError::Invalid { .. })));
+    assert!(matches!(Answers::parse("[day1"), Err(AnswersError::Toml(_))));
+}
+
+#[cfg(test)]
+macro_rules! verify_days {
+    ($($name:ident: $day:expr),+) => {
+        $(
+            #[test]
+            fn $name() {
+                use crate::input::{self, Source};
+
+                let answers = Answers::bundled().expect("answers load");
+                let input = input::load($day, &Source::Dir(Path::new(env!("CARGO_MANIFEST_DIR")).join("inputs")))
+                    .expect("input exists");
+
+                for &part in &[Part::One, Part::Two] {
+                    let verdict = answers.check($day, part, &input, &Options::default()).expect("correct parse");
+                    assert!(matches!(verdict, Verdict::Correct(_)), "day {} part {}: {}", $day, part.number(), verdict);
+                }
+            }
+        )+
+    }
+}
+
+#[cfg(test)]
+verify_days!(
+    day1: 1, day2: 2, day3: 3, day4: 4, day5: 5, day6: 6, day7: 7, day8: 8, day9: 9, day10: 10,
+    day11: 11, day12: 12, day13: 13, day14: 14, day15: 15, day16: 16, day17: 17, day18: 18, day19: 19,
+    day20: 20, day21: 21, day22: 22, day23: 23, day24: 24, day25: 25
+);
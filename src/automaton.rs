@@ -0,0 +1,319 @@
+use std::collections::{BTreeSet, HashMap};
+use std::collections::hash_map::DefaultHasher;
+use std::fmt;
+use std::hash::{Hash, Hasher};
+use std::str::FromStr;
+
+use smallvec::SmallVec;
+
+use crate::error::ParseError;
+use crate::grid::{Grid, Pos, HEX};
+
+/// Which live neighbour counts give birth to a dead cell and which keep a
+/// live one alive, written in B/S notation: `B3/S23` is Conway's life.
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+pub struct Rule {
+    birth: u16,
+    survival: u16
+}
+
+impl Rule {
+    pub fn born(self, neighbours: usize) -> bool {
+        neighbours < 9 && self.birth & 1 << neighbours != 0
+    }
+
+    pub fn survives(self, neighbours: usize) -> bool {
+        neighbours < 9 && self.survival & 1 << neighbours != 0
+    }
+
+    pub fn next(self, alive: bool, neighbours: usize) -> bool {
+        if alive { self.survives(neighbours) } else { self.born(neighbours) }
+    }
+}
+
+fn counts(prefix: char, text: &str) -> Result<u16, ParseError> {
+    let digits = text.strip_prefix(prefix).ok_or_else(|| ParseError::new(text, format!("`{}` and counts", prefix)))?;
+
+    digits.char_indices().try_fold(0, |mask, (pos, c)| match c.to_digit(10) {
+        Some(n) if n < 9 => Ok(mask | 1 << n),
+        _ => Err(ParseError::new(&digits[pos..pos + c.len_utf8()], "a count from 0 to 8"))
+    })
+}
+
+impl FromStr for Rule {
+    type Err = ParseError;
+
+    fn from_str(s: &str) -> Result<Self, ParseError> {
+        let mut halves = s.splitn(2, '/');
+        let birth = counts('B', halves.next().unwrap_or(s))?;
+        let survival = halves.next().ok_or_else(|| ParseError::end(s, "`/S` and counts"))?;
+
+        Ok(Rule { birth, survival: counts('S', survival)? })
+    }
+}
+
+impl fmt::Display for Rule {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let digits = |mask: u16| (0..9).filter(|n| mask & 1 << n != 0).map(|n| n.to_string()).collect::<String>();
+        write!(f, "B{}/S{}", digits(self.birth), digits(self.survival))
+    }
+}
+
+/// The cells an automaton lives on and how they are connected.
+pub trait Topology {
+    /// A configuration of live cells.
+    type State: Clone + Eq + Hash;
+
+    fn step(&self, rule: Rule, state: &Self::State) -> Self::State;
+
+    fn population(&self, state: &Self::State) -> usize;
+}
+
+/// Neighbours of a cell of a [`Dense`] topology.
+pub type Neighbours = SmallVec<[Pos; 8]>;
+
+/// A rectangle where each position is either no cell at all or a cell with
+/// fixed neighbours, not necessarily adjacent ones. The state has a flag for
+/// every position.
+#[derive(Clone, Debug)]
+pub struct Dense {
+    cells: Grid<Option<Neighbours>>
+}
+
+impl Dense {
+    pub fn new(cells: Grid<Option<Neighbours>>) -> Self {
+        Dense { cells }
+    }
+
+    /// Every position is a cell with up to eight neighbours around it.
+    pub fn moore(w: usize, h: usize) -> Self {
+        let grid = Grid::filled(w, h, ());
+        Dense::new(grid.map(|pos, _| Some(grid.neighbours8(pos).collect())))
+    }
+
+    /// A state of this shape where nothing lives.
+    pub fn empty(&self) -> Grid<bool> {
+        Grid::filled(self.cells.width(), self.cells.height(), false)
+    }
+}
+
+impl Topology for Dense {
+    type State = Grid<bool>;
+
+    fn step(&self, rule: Rule, state: &Grid<bool>) -> Grid<bool> {
+        self.cells.map(|pos, cell| {
+            cell.as_ref().is_some_and(|neighbours| {
+                rule.next(state[pos], neighbours.iter().filter(|&&n| state[n]).count())
+            })
+        })
+    }
+
+    fn population(&self, state: &Grid<bool>) -> usize {
+        state.cells().filter(|&&alive| alive).count()
+    }
+}
+
+/// Steps a state kept as the set of live cells of an unbounded lattice.
+fn sparse_step<C>(rule: Rule, live: &BTreeSet<C>, neighbours: impl Fn(&C, &mut dyn FnMut(C))) -> BTreeSet<C>
+    where C: Clone + Hash + Ord
+{
+    assert!(!rule.born(0), "{} brings infinitely many cells to life", rule);
+
+    let mut counts = HashMap::<C, usize>::new();
+
+    for cell in live {
+        neighbours(cell, &mut |neighbour| *counts.entry(neighbour).or_insert(0) += 1);
+    }
+
+    let lonely = live.iter()
+        .filter(|&cell| !counts.contains_key(cell) && rule.survives(0))
+        .cloned();
+
+    counts.iter()
+        .filter(|&(cell, &n)| rule.next(live.contains(cell), n))
+        .map(|(cell, _)| cell.clone())
+        .chain(lonely)
+        .collect()
+}
+
+pub type Point = SmallVec<[isize; 4]>;
+
+/// The unbounded square lattice in any number of dimensions, each cell
+/// touching the `3^dims - 1` around it.
+#[derive(Clone, Debug)]
+pub struct Cubic {
+    offsets: Vec<Point>
+}
+
+impl Cubic {
+    pub fn new(dims: usize) -> Self {
+        let mut offsets = vec![Point::new()];
+
+        for _ in 0..dims {
+            offsets = offsets.into_iter()
+                .flat_map(|head| (-1..=1).map(move |c| {
+                    let mut pt = head.clone();
+                    pt.push(c);
+                    pt
+                }))
+                .collect();
+        }
+
+        offsets.retain(|pt| pt.iter().any(|&c| c != 0));
+        Cubic { offsets }
+    }
+}
+
+impl Topology for Cubic {
+    type State = BTreeSet<Point>;
+
+    fn step(&self, rule: Rule, state: &BTreeSet<Point>) -> BTreeSet<Point> {
+        sparse_step(rule, state, |pt, neighbour| {
+            for d in self.offsets.iter() {
+                neighbour(pt.iter().zip(d).map(|(c, dc)| c + dc).collect());
+            }
+        })
+    }
+
+    fn population(&self, state: &BTreeSet<Point>) -> usize {
+        state.len()
+    }
+}
+
+/// The unbounded hex lattice in axial coordinates, see [`Grid::hex_neighbours`].
+#[derive(Clone, Copy, Debug)]
+pub struct Hex;
+
+impl Topology for Hex {
+    type State = BTreeSet<(isize, isize)>;
+
+    fn step(&self, rule: Rule, state: &Self::State) -> Self::State {
+        sparse_step(rule, state, |&(x, y), neighbour| {
+            for (dx, dy) in HEX.iter() {
+                neighbour((x + dx, y + dy));
+            }
+        })
+    }
+
+    fn population(&self, state: &Self::State) -> usize {
+        state.len()
+    }
+}
+
+/// A state seen again: from generation `start` on, states repeat every
+/// `period` generations, a fixed point having period 1.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub struct Cycle {
+    pub start: usize,
+    pub period: usize
+}
+
+/// Where [`Automaton::settle`] stopped.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Settled<S> {
+    /// The last state, the first one seen twice if there is a cycle.
+    pub state: S,
+    pub generations: usize,
+    /// `None` if the step limit came first.
+    pub cycle: Option<Cycle>,
+    pub population: usize
+}
+
+impl<S> Settled<S> {
+    pub fn is_fixed_point(&self) -> bool {
+        matches!(self.cycle, Some(Cycle { period: 1, .. }))
+    }
+}
+
+fn fingerprint(state: &impl Hash) -> u64 {
+    let mut hasher = DefaultHasher::new();
+    state.hash(&mut hasher);
+    hasher.finish()
+}
+
+#[derive(Clone, Debug)]
+pub struct Automaton<T> {
+    pub topology: T,
+    pub rule: Rule
+}
+
+impl<T: Topology> Automaton<T> {
+    pub fn new(topology: T, rule: Rule) -> Self {
+        Automaton { topology, rule }
+    }
+
+    pub fn step(&self, state: &T::State) -> T::State {
+        self.topology.step(self.rule, state)
+    }
+
+    pub fn population(&self, state: &T::State) -> usize {
+        self.topology.population(state)
+    }
+
+    /// The state after `generations` steps.
+    pub fn run(&self, state: &T::State, generations: usize) -> T::State {
+        (0..generations).fold(state.clone(), |state, _| self.step(&state))
+    }
+
+    /// Steps until a state repeats or `limit` generations have passed.
+    ///
+    /// Only hashes of past states are kept; a state whose hash was seen
+    /// before is compared with the earlier state, which is recomputed.
+    pub fn settle(&self, initial: &T::State, limit: Option<usize>) -> Settled<T::State> {
+        let mut seen = HashMap::new();
+        let mut state = initial.clone();
+        let mut generation = 0;
+
+        let cycle = loop {
+            let hash = fingerprint(&state);
+
+            if let Some(&start) = seen.get(&hash) {
+                if self.run(initial, start) == state {
+                    break Some(Cycle { start, period: generation - start });
+                }
+            } else {
+                seen.insert(hash, generation);
+            }
+
+            if limit.is_some_and(|limit| generation >= limit) {
+                break None;
+            }
+
+            state = self.step(&state);
+            generation += 1;
+        };
+
+        let population = self.population(&state);
+        Settled { state, generations: generation, cycle, population }
+    }
+}
+
+#[test]
+fn blinker() {
+    let rule: Rule = "B3/S23".parse().expect("valid rule");
+    assert_eq!(rule.to_string(), "B3/S23");
+    assert!(rule.born(3) && !rule.born(2) && rule.survives(2) && !rule.survives(4));
+    assert_eq!("B3/S2x".parse::<Rule>().unwrap_err().text, "x");
+    assert_eq!("B3".parse::<Rule>().unwrap_err().text, "");
+
+    let life = Automaton::new(Dense::moore(5, 5), rule);
+    let blinker = Grid::parse(".....\n.....\n.###.\n.....\n.....", "`.` or `#`", crate::grid::pixel).unwrap();
+
+    assert_eq!(life.step(&life.step(&blinker)), blinker);
+
+    let settled = life.settle(&blinker, None);
+    assert_eq!((settled.generations, settled.cycle, settled.population), (2, Some(Cycle { start: 0, period: 2 }), 3));
+    assert!(!settled.is_fixed_point());
+
+    let limited = life.settle(&blinker, Some(1));
+    assert_eq!((limited.generations, limited.cycle), (1, None));
+
+    let sparse = Automaton::new(Cubic::new(2), rule);
+    let row: BTreeSet<Point> = (0..3).map(|x| Point::from_slice(&[x, 0])).collect();
+    assert_eq!(sparse.settle(&row, None).cycle, Some(Cycle { start: 0, period: 2 }));
+
+    let pair: BTreeSet<Point> = (0..2).map(|x| Point::from_slice(&[x, 0])).collect();
+    let dead = sparse.settle(&pair, None);
+    assert!(dead.is_fixed_point());
+    assert_eq!((dead.cycle, dead.population), (Some(Cycle { start: 1, period: 1 }), 0));
+}
@@ -0,0 +1,115 @@
+use std::any::Any;
+use std::panic::{self, AssertUnwindSafe};
+use std::sync::Mutex;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::thread;
+use std::time::{Duration, Instant};
+
+use crate::Part;
+use crate::days::{self, Options};
+use crate::error::ParseError;
+use crate::solver::Answer;
+
+/// One part of one day to solve on the given input.
+#[derive(Clone, Copy, Debug)]
+pub struct Job<'a> {
+    pub day: usize,
+    pub part: Part,
+    pub input: &'a str,
+    pub options: &'a Options
+}
+
+#[derive(Debug)]
+pub enum Outcome {
+    /// Solved, `None` if the day has no such part.
+    Solved(Option<Answer>),
+    Failed(ParseError),
+    Panicked(String)
+}
+
+#[derive(Debug)]
+pub struct Done {
+    pub day: usize,
+    pub part: Part,
+    pub outcome: Outcome,
+    pub elapsed: Duration
+}
+
+fn panic_message(payload: Box<dyn Any + Send>) -> String {
+    if let Some(s) = payload.downcast_ref::<&str>() {
+        s.to_string()
+    } else if let Some(s) = payload.downcast_ref::<String>() {
+        s.clone()
+    } else {
+        "unknown panic".to_string()
+    }
+}
+
+fn solve(job: &Job<'_>) -> Done {
+    let start = Instant::now();
+    let solved = panic::catch_unwind(AssertUnwindSafe(|| days::solve_with(job.day, job.part, job.input, job.options)));
+    let elapsed = start.elapsed();
+
+    let outcome = match solved {
+        Ok(Ok(answer)) => Outcome::Solved(answer),
+        Ok(Err(err)) => Outcome::Failed(err),
+        Err(payload) => Outcome::Panicked(panic_message(payload))
+    };
+
+    Done { day: job.day, part: job.part, outcome, elapsed }
+}
+
+/// Default number of worker threads, one per available core.
+pub fn default_threads() -> usize {
+    thread::available_parallelism().map_or(1, |n| n.get())
+}
+
+/// Solves `jobs` on `threads` workers, returning them in the order given.
+///
+/// A job that panics is reported as [`Outcome::Panicked`] and does not
+/// affect the others.
+pub fn run(jobs: &[Job<'_>], threads: usize) -> Vec<Done> {
+    let next = AtomicUsize::new(0);
+    let done = Mutex::new(Vec::with_capacity(jobs.len()));
+
+    thread::scope(|scope| {
+        for _ in 0..threads.clamp(1, jobs.len().max(1)) {
+            scope.spawn(|| {
+                loop {
+                    let idx = next.fetch_add(1, Ordering::Relaxed);
+                    let job = match jobs.get(idx) {
+                        Some(job) => job,
+                        None => { break; }
+                    };
+
+                    let result = solve(job);
+                    done.lock().expect("no panics while locked").push((idx, result));
+                }
+            });
+        }
+    });
+
+    let mut done = done.into_inner().expect("no panics while locked");
+    done.sort_by_key(|&(idx, _)| idx);
+    done.into_iter().map(|(_, result)| result).collect()
+}
+
+#[test]
+fn isolated() {
+    let options = Options::default();
+    let jobs = [
+        Job { day: 1, part: Part::One, input: "1721\n979\n366\n299\n675\n1456", options: &options },
+        Job { day: 1, part: Part::Two, input: "1", options: &options },
+        Job { day: 8, part: Part::One, input: "jmp +x", options: &options }
+    ];
+
+    let done = run(&jobs, 2);
+
+    assert!(matches!(done[0].outcome, Outcome::Solved(Some(Answer::Signed(514579)))));
+    assert!(matches!(&done[1].outcome, Outcome::Panicked(msg) if msg == "summing triple exists"));
+    assert!(matches!(done[2].outcome, Outcome::Failed(_)));
+
+    let reversed: Vec<Job<'_>> = jobs.iter().rev().copied().collect();
+    let done = run(&reversed, 3);
+    assert_eq!(done.iter().map(|d| (d.day, d.part)).collect::<Vec<_>>(), [(8, Part::One), (1, Part::Two), (1, Part::One)]);
+}
@@ -0,0 +1,415 @@
+use std::env;
+use std::io;
+use std::path::PathBuf;
+use std::process;
+use std::time::Instant;
+
+use aoc2020::Part;
+use aoc2020::answers::{Answers, Verdict};
+use aoc2020::batch::{self, Job, Outcome};
+use aoc2020::days::{self, DAYS, Options, day4};
+use aoc2020::input::{self, Source};
+use aoc2020::report::{self, Format, Record};
+use aoc2020::schema::{Schema, SchemaError};
+
+const USAGE: &str = "usage: aoc run <day> [--part <1|2>] [--input <path|-> | --input-dir <dir>] [--format <text|json|tsv>]
+       aoc run <day> --stream [--part <1|2>] [--input <path|-> | --input-dir <dir>]
+       aoc run --all [--part <1|2>] [--input-dir <dir>] [--format <text|json|tsv>] [--jobs <n>]
+       aoc report <day> [--part <1|2>] [--input <path|-> | --input-dir <dir>] [--format <text|json|dot>] [--schema <path>]
+       aoc verify [<day>] [--part <1|2>] [--input-dir <dir>] [--answers <path>]";
+
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+enum Command {
+    Run,
+    Report,
+    Verify
+}
+
+#[derive(Debug)]
+struct Args {
+    command: Command,
+    days: Vec<usize>,
+    parts: Vec<Part>,
+    source: Source,
+    answers: Option<PathBuf>,
+    schema: Option<PathBuf>,
+    format: Format,
+    jobs: usize,
+    all: bool,
+    stream: bool
+}
+
+fn parse_part(s: &str) -> Option<Part> {
+    match s {
+        "1" => Some(Part::One),
+        "2" => Some(Part::Two),
+        _ => None
+    }
+}
+
+fn parse_args(mut args: impl Iterator<Item=String>) -> Result<Args, String> {
+    let command = match args.next().as_deref() {
+        Some("run") => Command::Run,
+        Some("report") => Command::Report,
+        Some("verify") => Command::Verify,
+        Some(cmd) => { return Err(format!("unknown command: {}", cmd)); },
+        None => { return Err("missing command".to_string()); }
+    };
+
+    let mut day = None;
+    let mut part = None;
+    let mut source = Source::Default;
+    let mut answers = None;
+    let mut schema = None;
+    let mut format = Format::Text;
+    let mut jobs = batch::default_threads();
+    let mut all = false;
+    let mut stream = false;
+
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "--all" => { all = true; },
+            "--stream" if command == Command::Run => { stream = true; },
+            "--part" | "-p" => {
+                let value = args.next().ok_or("--part needs a value")?;
+                part = Some(parse_part(&value).ok_or_else(|| format!("invalid part: {}", value))?);
+            },
+            "--input" | "-i" => {
+                let value = args.next().ok_or("--input needs a path or -")?;
+                source = Source::from_arg(&value);
+            },
+            "--input-dir" => {
+                let value = args.next().ok_or("--input-dir needs a directory")?;
+                source = Source::Dir(value.into());
+            },
+            "--format" | "-f" if command != Command::Verify => {
+                let value = args.next().ok_or("--format needs text, json or tsv")?;
+                format = value.parse()?;
+            },
+            "--jobs" | "-j" if command == Command::Run => {
+                let value = args.next().ok_or("--jobs needs a number")?;
+                jobs = value.parse().ok().filter(|&n| n > 0).ok_or_else(|| format!("invalid number of jobs: {}", value))?;
+            },
+            "--answers" if command == Command::Verify => {
+                let value = args.next().ok_or("--answers needs a path")?;
+                answers = Some(value.into());
+            },
+            "--schema" if command == Command::Report => {
+                let value = args.next().ok_or("--schema needs a path")?;
+                schema = Some(value.into());
+            },
+            _ if day.is_none() && !arg.starts_with('-') => {
+                let value = arg.parse::<usize>().ok().filter(|d| (1..=DAYS).contains(d));
+                day = Some(value.ok_or_else(|| format!("invalid day: {}", arg))?);
+            },
+            _ => { return Err(format!("unexpected argument: {}", arg)); }
+        }
+    }
+
+    if command == Command::Verify && day.is_none() {
+        all = true;
+    }
+
+    let days = match (day, all) {
+        (Some(day), false) => vec![day],
+        (None, true) if matches!(source, Source::Path(_) | Source::Stdin) => {
+            return Err("--all reads every day from a directory, use --input-dir".to_string());
+        },
+        (None, true) => (1..=DAYS).collect(),
+        (Some(_), true) => { return Err("either a day or --all, not both".to_string()); },
+        (None, false) => { return Err("missing day".to_string()); }
+    };
+
+    if command == Command::Report {
+        if all {
+            return Err("report explains a single day".to_string());
+        }
+
+        if format == Format::Tsv {
+            return Err("report only prints text, json or dot".to_string());
+        }
+
+        if schema.is_some() && day != Some(4) {
+            return Err("only day 4 checks records against a schema".to_string());
+        }
+    } else if format == Format::Dot {
+        return Err("only report prints dot".to_string());
+    }
+
+    if stream && all {
+        return Err("--stream solves a single day".to_string());
+    }
+
+    let parts = match (command, part) {
+        (Command::Report, None) => vec![Part::One],
+        // Only the parts a day can stream, so that the default never fails.
+        (_, None) if stream => days::streamed_parts(days[0]).to_vec(),
+        _ => part.map_or_else(|| vec![Part::One, Part::Two], |p| vec![p])
+    };
+
+    if stream {
+        if let Some(part) = parts.iter().find(|p| !days::streamed_parts(days[0]).contains(p)) {
+            return Err(format!("day {} part {} cannot be streamed", days[0], part.number()));
+        }
+
+        if parts.is_empty() {
+            return Err(format!("day {} cannot be streamed", days[0]));
+        }
+
+        if format != Format::Text {
+            return Err("--stream only prints text".to_string());
+        }
+
+        if source == Source::Stdin && parts.len() > 1 {
+            return Err("--stream reads stdin only once, pick a --part".to_string());
+        }
+    }
+
+    Ok(Args { command, days, parts, source, answers, schema, format, jobs, all, stream })
+}
+
+/// Options for the days in `args`, only reading the files of the days being
+/// solved so that a broken `passport.toml` only stops day 4.
+fn options(args: &Args) -> Result<Options, SchemaError> {
+    if args.days.contains(&4) {
+        Options::load(&args.source)
+    } else {
+        Ok(Options::default())
+    }
+}
+
+fn run(args: &Args) {
+    let mut failed = 0;
+    let mut inputs = Vec::new();
+
+    for &day in args.days.iter() {
+        match input::load(day, &args.source) {
+            Ok(input) => inputs.push((day, input)),
+            Err(err) if args.all => {
+                eprintln!("error: {}", err);
+                failed += 1;
+            },
+            Err(err) => {
+                eprintln!("error: {}", err);
+                process::exit(1);
+            }
+        }
+    }
+
+    let options = match options(args) {
+        Ok(options) => options,
+        Err(err) if args.all => {
+            eprintln!("error: day 4: {}", err);
+            inputs.retain(|&(day, _)| day != 4);
+            failed += 1;
+            Options::default()
+        },
+        Err(err) => {
+            eprintln!("error: {}", err);
+            process::exit(1);
+        }
+    };
+
+    let options = &options;
+    let jobs: Vec<_> = inputs.iter()
+        .flat_map(|(day, input)| args.parts.iter().map(move |&part| Job { day: *day, part, input, options }))
+        .collect();
+
+    let start = Instant::now();
+    let done = batch::run(&jobs, args.jobs);
+    let wall = start.elapsed();
+
+    let input_of = |day| inputs.iter().find(|(d, _)| *d == day).map(|(_, input)| input.as_str()).unwrap_or("");
+    let mut records = Vec::new();
+    let mut reported = None;
+
+    for d in done.iter() {
+        match &d.outcome {
+            Outcome::Solved(Some(answer)) if args.format != Format::Text => {
+                let input_hash = input::hash(input_of(d.day));
+                records.push(Record { day: d.day, part: d.part, answer: answer.clone(), elapsed: d.elapsed, input_hash });
+            },
+            Outcome::Solved(Some(answer)) if !args.all => println!("{}", answer),
+            Outcome::Solved(_) if args.all || args.parts.len() > 1 => {},
+            Outcome::Solved(_) => {
+                eprintln!("error: day {} has no part {}", d.day, d.part.number());
+                failed += 1;
+            },
+            // Both parts of a day fail to parse the same way.
+            Outcome::Failed(_) if reported == Some(d.day) => { failed += 1; },
+            Outcome::Failed(err) => {
+                reported = Some(d.day);
+                eprintln!("error: {}", err);
+                if let Some(excerpt) = err.excerpt(input_of(d.day)) {
+                    eprintln!("{}", excerpt);
+                }
+
+                failed += 1;
+            },
+            Outcome::Panicked(msg) => {
+                eprintln!("error: day {} part {} panicked: {}", d.day, d.part.number(), msg);
+                failed += 1;
+            }
+        }
+    }
+
+    let written = match args.format {
+        Format::Text if args.all => report::write_table(&mut io::stdout(), &done, wall, args.jobs),
+        Format::Text | Format::Dot => Ok(()),
+        Format::Json => report::write_json(&mut io::stdout(), &records),
+        Format::Tsv => report::write_tsv(&mut io::stdout(), &records)
+    };
+
+    if let Err(err) = written {
+        eprintln!("error: {}", err);
+        process::exit(1);
+    }
+
+    if failed > 0 {
+        process::exit(1);
+    }
+}
+
+/// Solves each part reading the input afresh, a line at a time.
+fn run_stream(args: &Args) {
+    let day = args.days[0];
+
+    for &part in args.parts.iter() {
+        let mut reader = input::open(day, &args.source).unwrap_or_else(|err| {
+            eprintln!("error: {}", err);
+            process::exit(1);
+        });
+
+        match days::solve_stream(day, part, &mut *reader) {
+            Ok(Some(answer)) => println!("{}", answer),
+            Ok(None) => {
+                eprintln!("error: day {} part {} cannot be streamed", day, part.number());
+                process::exit(1);
+            },
+            Err(err) => {
+                eprintln!("error: {}", err);
+                process::exit(1);
+            }
+        }
+    }
+}
+
+/// Prints the details a day gives on how it solved a part.
+fn report(args: &Args) {
+    let day = args.days[0];
+    let input = input::load(day, &args.source).unwrap_or_else(|err| {
+        eprintln!("error: {}", err);
+        process::exit(1);
+    });
+
+    let reported = if day == 4 {
+        let loaded = match &args.schema {
+            Some(path) => Schema::load(path),
+            None => day4::find_schema(&args.source).map(|found| found.unwrap_or_else(|| day4::passport_schema().clone()))
+        };
+
+        let schema = loaded.unwrap_or_else(|err| {
+            eprintln!("error: {}", err);
+            process::exit(1);
+        });
+
+        day4::report_against(&schema, args.parts[0], &input, args.format).map(Some)
+    } else {
+        days::report(day, args.parts[0], &input, args.format)
+    };
+
+    match reported {
+        Ok(Some(report)) => print!("{}", report),
+        Ok(None) => {
+            eprintln!("error: day {} has no report", day);
+            process::exit(1);
+        },
+        Err(err) => {
+            eprintln!("error: {}", err);
+            if let Some(excerpt) = err.excerpt(&input) {
+                eprintln!("{}", excerpt);
+            }
+
+            process::exit(1);
+        }
+    }
+}
+
+fn verify(args: &Args) {
+    let answers = match &args.answers {
+        Some(path) => Answers::load(path),
+        None => Answers::bundled()
+    };
+
+    let answers = answers.unwrap_or_else(|err| {
+        eprintln!("error: {}", err);
+        process::exit(1);
+    });
+
+    let mut failed = 0;
+    let mut days = args.days.clone();
+
+    let options = options(args).unwrap_or_else(|err| {
+        println!("day {:2}: error: {}", 4, err);
+        days.retain(|&day| day != 4);
+        failed += 1;
+        Options::default()
+    });
+
+    for &day in days.iter() {
+        let input = match input::load(day, &args.source) {
+            Ok(input) => input,
+            Err(err) => {
+                println!("day {:2}: error: {}", day, err);
+                failed += 1;
+                continue;
+            }
+        };
+
+        for &part in args.parts.iter() {
+            match answers.check(day, part, &input, &options) {
+                Ok(Verdict::Correct(None)) => {},
+                Ok(verdict @ Verdict::Correct(_)) => println!("day {:2} part {}: {}", day, part.number(), verdict),
+                Ok(verdict) => {
+                    println!("day {:2} part {}: {}", day, part.number(), verdict);
+                    failed += 1;
+                },
+                Err(err) => {
+                    println!("day {:2} part {}: error: {}", day, part.number(), err);
+                    failed += 1;
+                }
+            }
+        }
+    }
+
+    if failed > 0 {
+        eprintln!("error: {} check(s) failed", failed);
+        process::exit(1);
+    }
+}
+
+fn main() {
+    let args = parse_args(env::args().skip(1)).unwrap_or_else(|err| {
+        eprintln!("error: {}\n{}", err, USAGE);
+        process::exit(2);
+    });
+
+    match args.command {
+        Command::Run if args.stream => run_stream(&args),
+        Command::Run => run(&args),
+        Command::Report => report(&args),
+        Command::Verify => verify(&args)
+    }
+}
+
+#[test]
+fn streamed_parts() {
+    let parse = |line: &str| parse_args(line.split_whitespace().map(str::to_string));
+
+    assert_eq!(parse("run 1 --stream").unwrap().parts, [Part::One]);
+    assert_eq!(parse("run 9 --stream --input -").unwrap().parts, [Part::One]);
+    assert_eq!(parse("run 2 --stream").unwrap().parts, [Part::One, Part::Two]);
+    assert_eq!(parse("run 1 --stream --part 2").unwrap_err(), "day 1 part 2 cannot be streamed");
+    assert_eq!(parse("run 3 --stream").unwrap_err(), "day 3 cannot be streamed");
+    assert_eq!(parse("run 1").unwrap().parts, [Part::One, Part::Two]);
+}
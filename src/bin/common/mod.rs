@@ -0,0 +1,37 @@
+//! What every per-day binary does: solve both parts and print the answers.
+
+use std::process;
+
+use aoc2020::Part;
+use aoc2020::days::{self, Options};
+use aoc2020::input::{self, Source};
+
+/// Prints the answers to both parts of `day`, exiting with an error if the
+/// input cannot be read or parsed.
+pub fn print(day: usize, source: &Source) {
+    let input = input::load(day, source).unwrap_or_else(|err| {
+        eprintln!("error: {}", err);
+        process::exit(1);
+    });
+
+    let options = if day == 4 { Options::load(source) } else { Ok(Options::default()) };
+    let options = options.unwrap_or_else(|err| {
+        eprintln!("error: {}", err);
+        process::exit(1);
+    });
+
+    for &part in &[Part::One, Part::Two] {
+        match days::solve_with(day, part, &input, &options) {
+            Ok(Some(answer)) => println!("{}", answer),
+            Ok(None) => {},
+            Err(err) => {
+                eprintln!("error: {}", err);
+                if let Some(excerpt) = err.excerpt(&input) {
+                    eprintln!("{}", excerpt);
+                }
+
+                process::exit(1);
+            }
+        }
+    }
+}
@@ -0,0 +1,276 @@
+//! Day 1: Report Repair.
+
+use std::collections::{HashMap, HashSet};
+use std::io::BufRead;
+use itertools::Itertools;
+
+use crate::Part;
+use crate::error::{ParseError, parse_as};
+use crate::solver::{Answer, Solver};
+use crate::stream::{self, StreamError};
+
+fn entry(line: &str) -> Result<i64, ParseError> {
+    parse_as(line, "an entry")
+}
+
+fn entries(input: &str) -> Result<Vec<i64>, ParseError> {
+    input.lines().map(|s| entry(s.trim())).collect()
+}
+
+/// An entry and the 1-based line it is on.
+type Entry = (i64, usize);
+
+/// Entries adding up to a target, in increasing order of value.
+#[derive(Clone, Debug, Eq, Ord, PartialEq, PartialOrd)]
+pub struct Combination {
+    pub values: Vec<i64>,
+    /// 1-based line of each value.
+    pub lines: Vec<usize>
+}
+
+impl Combination {
+    fn new(mut entries: Vec<Entry>) -> Self {
+        entries.sort_unstable();
+        let (values, lines) = entries.into_iter().unzip();
+
+        Combination { values, lines }
+    }
+
+    pub fn product(&self) -> i64 {
+        self.values.iter().product()
+    }
+}
+
+/// From this many entries per combination on, [`summing_k`] meets in the
+/// middle instead of narrowing down sorted entries with two pointers.
+const MEET_IN_THE_MIDDLE: usize = 4;
+
+/// Every distinct combination of `k` entries adding up to `target`, each
+/// entry used at most as often as it appears. Combinations are told apart by
+/// their values, and come sorted by them.
+pub fn summing_k(entries: &[i64], k: usize, target: i64) -> Vec<Combination> {
+    let mut found = search(entries, k, target, false);
+    found.sort_unstable();
+    found
+}
+
+/// The first combination [`summing_k`] comes across.
+pub fn first_summing_k(entries: &[i64], k: usize, target: i64) -> Option<Combination> {
+    search(entries, k, target, true).pop()
+}
+
+fn search(entries: &[i64], k: usize, target: i64, first: bool) -> Vec<Combination> {
+    let mut sorted: Vec<Entry> = entries.iter().copied().zip(1..).collect();
+    sorted.sort_unstable();
+
+    let mut found = Vec::new();
+
+    if k >= MEET_IN_THE_MIDDLE {
+        meet_in_the_middle(&sorted, k, target.into(), first, &mut found);
+    } else {
+        two_pointers(&sorted, k, target.into(), first, &mut Vec::new(), &mut found);
+    }
+
+    found
+}
+
+fn total(entries: &[Entry]) -> i128 {
+    entries.iter().map(|&(v, _)| i128::from(v)).sum()
+}
+
+/// Fixes the smallest entry until two are left, then closes in on those from
+/// both ends. Returns whether to stop searching.
+fn two_pointers(sorted: &[Entry], k: usize, target: i128, first: bool, prefix: &mut Vec<Entry>, found: &mut Vec<Combination>) -> bool {
+    let mut push = |tail: &[Entry]| {
+        found.push(Combination::new(prefix.iter().chain(tail).copied().collect()));
+        first
+    };
+
+    match k {
+        0 => target == 0 && push(&[]),
+        1 => {
+            let pos = sorted.binary_search_by(|&(v, _)| i128::from(v).cmp(&target));
+            pos.is_ok_and(|pos| push(&sorted[pos..=pos]))
+        },
+        2 => {
+            let (mut lo, mut hi) = (0, sorted.len());
+
+            while lo + 1 < hi {
+                let sum = total(&[sorted[lo], sorted[hi - 1]]);
+
+                if sum == target {
+                    if push(&[sorted[lo], sorted[hi - 1]]) {
+                        return true;
+                    }
+
+                    let (low, high) = (sorted[lo].0, sorted[hi - 1].0);
+                    while lo < hi && sorted[lo].0 == low { lo += 1; }
+                    while lo < hi && sorted[hi - 1].0 == high { hi -= 1; }
+                } else if sum < target {
+                    lo += 1;
+                } else {
+                    hi -= 1;
+                }
+            }
+
+            false
+        },
+        _ => {
+            for i in 0..sorted.len().saturating_sub(k - 1) {
+                // The smallest and largest sums with this entry as the smallest one.
+                if total(&sorted[i..i + k]) > target {
+                    break;
+                }
+
+                if total(&sorted[i..=i]) + total(&sorted[sorted.len() + 1 - k..]) < target
+                    || (i > 0 && sorted[i].0 == sorted[i - 1].0) {
+                    continue;
+                }
+
+                prefix.push(sorted[i]);
+                let stop = two_pointers(&sorted[i + 1..], k - 1, target - i128::from(sorted[i].0), first, prefix, found);
+                prefix.pop();
+
+                if stop {
+                    return true;
+                }
+            }
+
+            false
+        }
+    }
+}
+
+/// Splits each combination of entries into its lower and upper half by
+/// position in `sorted`, keeping every lower half by sum and looking it up
+/// from the upper ones.
+fn meet_in_the_middle(sorted: &[Entry], k: usize, target: i128, first: bool, found: &mut Vec<Combination>) {
+    let sum = |idx: &[usize]| idx.iter().map(|&i| i128::from(sorted[i].0)).sum::<i128>();
+
+    let mut lower = HashMap::<i128, Vec<Vec<usize>>>::new();
+    for idx in (0..sorted.len()).combinations(k / 2) {
+        lower.entry(sum(&idx)).or_default().push(idx);
+    }
+
+    let mut seen = HashSet::new();
+
+    for upper in (0..sorted.len()).combinations(k - k / 2) {
+        let halves = lower.get(&(target - sum(&upper))).into_iter().flatten();
+
+        for low in halves.filter(|low| low.last() < upper.first()) {
+            let entries: Vec<Entry> = low.iter().chain(&upper).map(|&i| sorted[i]).collect();
+
+            if seen.insert(entries.iter().map(|&(v, _)| v).collect::<Vec<_>>()) {
+                found.push(Combination::new(entries));
+
+                if first {
+                    return;
+                }
+            }
+        }
+    }
+}
+
+/// First pair of entries adding up to `sum`, read one per line, remembering
+/// the first line each value was seen on.
+pub fn summing_pair_stream(reader: impl BufRead, sum: i64) -> Result<Option<Combination>, StreamError> {
+    let mut seen = HashMap::new();
+
+    for (entry, line) in stream::parse_lines(Day1::DAY, reader, entry).zip(1..) {
+        let entry = entry?;
+
+        if let Some(other) = sum.checked_sub(entry).and_then(|v| seen.get(&v).map(|&l| (v, l))) {
+            return Ok(Some(Combination::new(vec![other, (entry, line)])));
+        }
+
+        seen.entry(entry).or_insert(line);
+    }
+
+    Ok(None)
+}
+
+/// Solves part one reading the input from `reader` a line at a time. Memory
+/// grows with the number of distinct entries, not with the number of lines.
+/// Part two would have to keep a sum for every pair of them, so it is not
+/// streamed.
+pub fn stream(part: Part, reader: &mut dyn BufRead) -> Result<Option<Answer>, StreamError> {
+    match part {
+        Part::One => Ok(Some(summing_pair_stream(reader, 2020)?.expect("summing pair exists").product().into())),
+        Part::Two => Ok(None)
+    }
+}
+
+pub struct Day1;
+
+impl Solver for Day1 {
+    const DAY: usize = 1;
+
+    type Input = Vec<i64>;
+    type One = i64;
+    type Two = i64;
+
+    fn parse(input: &str) -> Result<Self::Input, ParseError> {
+        entries(input)
+    }
+
+    fn part_one(entries: &Self::Input) -> i64 {
+        first_summing_k(entries, 2, 2020).expect("summing pair exists").product()
+    }
+
+    fn part_two(entries: &Self::Input) -> i64 {
+        first_summing_k(entries, 3, 2020).expect("summing triple exists").product()
+    }
+}
+
+/// Example from the puzzle text.
+pub const EXAMPLE: &str = "1721\n979\n366\n299\n675\n1456";
+
+#[test]
+fn example() {
+    let entries = Day1::parse(EXAMPLE).expect("correct parse");
+    assert_eq!(Day1::part_one(&entries), 514579);
+    assert_eq!(Day1::part_two(&entries), 241861950);
+
+    let pair = summing_pair_stream(EXAMPLE.as_bytes(), 2020).unwrap().expect("exists");
+    assert_eq!((pair.values, pair.lines), (vec![299, 1721], vec![4, 1]));
+    assert_eq!(stream(Part::Two, &mut EXAMPLE.as_bytes()).unwrap(), None);
+}
+
+#[test]
+fn k_sums() {
+    let values = |found: Vec<Combination>| found.into_iter().map(|c| c.values).collect::<Vec<_>>();
+
+    assert_eq!(values(summing_k(&[1721, 979, 366, 299, 675, 1456], 2, 2020)), vec![vec![299, 1721]]);
+    assert_eq!(values(summing_k(&[1721, 979, 366, 299, 675, 1456], 3, 2020)), vec![vec![366, 675, 979]]);
+
+    let entries = [5, 1, 4, 3, 2, 1, 3];
+    assert_eq!(values(summing_k(&entries, 4, 10)), vec![vec![1, 1, 3, 5], vec![1, 2, 3, 4]]);
+
+    let mut sorted: Vec<Entry> = [5, -1, 4, 3, -2, 1, 3].iter().copied().zip(1..).collect();
+    sorted.sort_unstable();
+
+    for k in 1..=5 {
+        for target in -5..=20 {
+            let mut slow = Vec::new();
+            two_pointers(&sorted, k, target, false, &mut Vec::new(), &mut slow);
+
+            let mut fast = Vec::new();
+            meet_in_the_middle(&sorted, k, target, false, &mut fast);
+
+            slow.sort_unstable();
+            fast.sort_unstable();
+            assert_eq!(values(slow), values(fast), "k = {}, target = {}", k, target);
+        }
+    }
+}
+
+#[test]
+fn multiplicities() {
+    let triple = first_summing_k(&[1000, 20, 7, 1000], 3, 2020).expect("exists");
+    assert_eq!((triple.values, triple.lines), (vec![20, 1000, 1000], vec![2, 1, 4]));
+    assert_eq!(first_summing_k(&[1000, 20, 7], 3, 2020), None);
+
+    let refund = summing_k(&[2030, -10, 1000, 1020, 5], 2, 2020);
+    assert_eq!(refund.iter().map(|c| c.lines.clone()).collect::<Vec<_>>(), vec![vec![2, 1], vec![3, 4]]);
+    assert_eq!(Day1::parse("-15\n2035").map(|e| Day1::part_one(&e)), Ok(-30525));
+}
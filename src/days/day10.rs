@@ -0,0 +1,195 @@
+//! Day 10: Adapter Array.
+
+use std::collections::{BTreeMap, VecDeque};
+use std::io::BufRead;
+use std::iter;
+use num::bigint::BigUint;
+
+use crate::Part;
+use crate::error::{ParseError, parse_as};
+use crate::solver::{Answer, Solver};
+use crate::stream::{self, StreamError};
+
+fn joltage(line: &str) -> Result<usize, ParseError> {
+    parse_as(line, "a joltage")
+}
+
+fn joltages(input: &str) -> Result<Vec<usize>, ParseError> {
+    input.lines().map(|s| joltage(s.trim())).collect()
+}
+
+/// How many adapters there are of each joltage.
+pub type Counts = BTreeMap<usize, usize>;
+
+pub fn counts(jolts: &[usize]) -> Counts {
+    let mut counts = Counts::new();
+
+    for &jolt in jolts {
+        *counts.entry(jolt).or_insert(0) += 1;
+    }
+
+    counts
+}
+
+/// Joltages read one per line, counted. Memory grows with the number of
+/// distinct joltages, not with the number of lines.
+pub fn joltages_stream(reader: impl BufRead) -> Result<Counts, StreamError> {
+    let mut counts = Counts::new();
+
+    for jolt in stream::parse_lines(Day10::DAY, reader, joltage) {
+        *counts.entry(jolt?).or_insert(0) += 1;
+    }
+
+    Ok(counts)
+}
+
+/// Solves `part` reading the input from `reader`, see [`joltages_stream`].
+pub fn stream(part: Part, reader: &mut dyn BufRead) -> Result<Option<Answer>, StreamError> {
+    let counts = joltages_stream(reader)?;
+
+    let answer = match part {
+        Part::One => chain_distribution_of(&counts).expect("exists").into(),
+        Part::Two => arrangements_of(&counts).into()
+    };
+
+    Ok(Some(answer))
+}
+
+/// Product of the 1-jolt and 3-jolt differences in the chain using every adapter.
+pub fn chain_distribution(jolts: &[usize]) -> Option<usize> {
+    chain_distribution_of(&counts(jolts))
+}
+
+/// Like [`chain_distribution`], from how many adapters there are of each
+/// joltage. Two adapters of the same joltage cannot follow each other.
+pub fn chain_distribution_of(counts: &Counts) -> Option<usize> {
+    if counts.values().any(|&n| n > 1) {
+        return None;
+    }
+
+    let device = counts.keys().next_back()?.checked_add(3)?;
+    let mut diffs = [0usize; 3];
+    let mut previous = 0;
+
+    for &jolt in counts.keys().chain(iter::once(&device)) {
+        match jolt - previous {
+            diff @ 1..=3 => { diffs[diff - 1] += 1; },
+            _ => { return None; }
+        }
+
+        previous = jolt;
+    }
+
+    Some(diffs[0] * diffs[2])
+}
+
+/// Number of distinct adapter arrangements from the outlet to the device.
+pub fn arrangements(jolts: &[usize]) -> BigUint {
+    arrangements_of(&counts(jolts))
+}
+
+/// Like [`arrangements`], from how many adapters there are of each joltage.
+///
+/// Adapters of the same joltage are told apart and may follow each other in
+/// the order read, so with `p` ways to reach a joltage just below, the `k`th
+/// of them is reached in `2^(k-1) p` ways and all of them in `(2^n - 1) p`.
+pub fn arrangements_of(counts: &Counts) -> BigUint {
+    // Ways to reach each of the last few joltages, starting from the outlet.
+    let mut window: VecDeque<(usize, BigUint)> = VecDeque::new();
+    window.push_back((0, 1u32.into()));
+    let mut last = BigUint::from(1u32);
+
+    for (&jolt, &n) in counts {
+        while window.front().is_some_and(|&(j, _)| j + 3 < jolt) {
+            window.pop_front();
+        }
+
+        let below: BigUint = window.iter().map(|(_, ways)| ways).sum();
+        let all = ((BigUint::from(1u32) << n) - 1u32) * &below;
+        last = (BigUint::from(1u32) << (n - 1)) * below;
+        window.push_back((jolt, all));
+    }
+
+    last
+}
+
+pub struct Day10;
+
+impl Solver for Day10 {
+    const DAY: usize = 10;
+
+    type Input = Vec<usize>;
+    type One = usize;
+    type Two = BigUint;
+
+    fn parse(input: &str) -> Result<Vec<usize>, ParseError> {
+        joltages(input)
+    }
+
+    fn part_one(jolts: &Vec<usize>) -> usize {
+        chain_distribution(jolts).expect("exists")
+    }
+
+    fn part_two(jolts: &Vec<usize>) -> BigUint {
+        arrangements(jolts)
+    }
+}
+
+/// Example from the puzzle text.
+pub const EXAMPLE: &str = r"28
+33
+18
+42
+31
+14
+46
+20
+48
+47
+24
+23
+49
+45
+19
+38
+39
+11
+1
+32
+25
+35
+8
+17
+7
+9
+4
+2
+34
+10
+3";
+
+#[test]
+fn example() {
+    let jolts = Day10::parse(EXAMPLE).expect("correct parse");
+    assert_eq!(Day10::part_one(&jolts), 220);
+    assert_eq!(Day10::part_two(&jolts), BigUint::from(19208u32));
+
+    assert_eq!(joltages_stream(EXAMPLE.as_bytes()).unwrap(), counts(&jolts));
+}
+
+#[test]
+fn stream_matches_solver() {
+    let jolts = Day10::parse(EXAMPLE).expect("correct parse");
+    assert_eq!(stream(Part::One, &mut EXAMPLE.as_bytes()).unwrap(), Some(Day10::part_one(&jolts).into()));
+    assert_eq!(stream(Part::Two, &mut EXAMPLE.as_bytes()).unwrap(), Some(Day10::part_two(&jolts).into()));
+
+    // Both 1-jolt adapters count, each in its own arrangements.
+    let repeated = "1\n1\n2\n5";
+    let jolts = Day10::parse(repeated).expect("correct parse");
+    assert_eq!(Day10::part_two(&jolts), BigUint::from(4u32));
+    assert_eq!(stream(Part::Two, &mut repeated.as_bytes()).unwrap(), Some(Day10::part_two(&jolts).into()));
+    assert_eq!(chain_distribution(&jolts), None);
+
+    assert_eq!(arrangements(&[0, 0, 3, 3]), BigUint::from(8u32));
+    assert_eq!(arrangements(&[]), BigUint::from(1u32));
+}
@@ -0,0 +1,132 @@
+//! Day 11: Seating System.
+
+use crate::automaton::{Automaton, Dense, Neighbours, Rule, Settled};
+use crate::error::ParseError;
+use crate::grid::{Grid, Pos, ToChar};
+use crate::solver::Solver;
+
+/// Rounds after which a seating that keeps changing is given up on.
+const ROUND_LIMIT: usize = 10_000;
+
+#[derive(Clone, Copy, Eq, PartialEq, Debug)]
+pub enum Seat {
+    Floor,
+    Empty,
+    Occupied
+}
+
+impl Seat {
+    fn from_char(c: char) -> Option<Seat> {
+        match c {
+            'L' => Some(Seat::Empty),
+            '.' => Some(Seat::Floor),
+            _ => None
+        }
+    }
+}
+
+impl ToChar for Seat {
+    fn to_char(&self) -> char {
+        match self {
+            Seat::Occupied => '#',
+            Seat::Empty => 'L',
+            Seat::Floor => '.'
+        }
+    }
+}
+
+/// Seat layout of the waiting area.
+pub type Seats = Grid<Seat>;
+
+/// The seats as cells of an automaton, each watching the seats `watched` gives.
+fn seating(seats: &Seats, watched: impl Fn(Pos) -> Neighbours) -> Dense {
+    Dense::new(seats.map(|pos, &seat| if seat == Seat::Floor { None } else { Some(watched(pos)) }))
+}
+
+/// People look at the eight adjacent seats.
+pub fn watched_near(seats: &Seats) -> Dense {
+    seating(seats, |pos| seats.neighbours8(pos).filter(|&p| seats[p] != Seat::Floor).collect())
+}
+
+/// People look at the first seat in each of the eight directions.
+pub fn watched_los(seats: &Seats) -> Dense {
+    seating(seats, |pos| seats.line_of_sight(pos, |&s| s == Seat::Floor).collect())
+}
+
+/// People take a seat when none of the watched seats are taken and leave
+/// once `tolerance` of them are.
+pub fn rule(tolerance: usize) -> Rule {
+    let stay: String = (0..tolerance).map(|n| n.to_string()).collect();
+    format!("B0/S{}", stay).parse().expect("valid rule")
+}
+
+/// Runs the seating, empty at first, until it repeats itself or `limit`
+/// rounds have passed.
+pub fn simulate(seating: Dense, tolerance: usize, limit: Option<usize>) -> Settled<Grid<bool>> {
+    let automaton = Automaton::new(seating, rule(tolerance));
+    automaton.settle(&automaton.topology.empty(), limit)
+}
+
+/// Occupied seats once the seating stops changing.
+fn occupied_when_stable(seating: Dense, tolerance: usize) -> usize {
+    let settled = simulate(seating, tolerance, Some(ROUND_LIMIT));
+    assert!(settled.is_fixed_point(), "seating does not settle: {:?}", settled.cycle);
+
+    settled.population
+}
+
+fn grid(input: &str) -> Result<Seats, ParseError> {
+    Grid::parse(input, "`L` or `.`", Seat::from_char)
+}
+
+pub struct Day11;
+
+impl Solver for Day11 {
+    const DAY: usize = 11;
+
+    type Input = Seats;
+    type One = usize;
+    type Two = usize;
+
+    fn parse(input: &str) -> Result<Seats, ParseError> {
+        grid(input)
+    }
+
+    fn part_one(seats: &Seats) -> usize {
+        occupied_when_stable(watched_near(seats), 4)
+    }
+
+    fn part_two(seats: &Seats) -> usize {
+        occupied_when_stable(watched_los(seats), 5)
+    }
+}
+
+/// Example from the puzzle text.
+pub const EXAMPLE: &str = r"L.LL.LL.LL
+LLLLLLL.LL
+L.L.L..L..
+LLLL.LL.LL
+L.LL.LL.LL
+L.LLLLL.LL
+..L.L.....
+LLLLLLLLLL
+L.LLLLLL.L
+L.LLLLL.LL";
+
+#[test]
+fn example() {
+    let grid = Day11::parse(EXAMPLE).expect("correct parse");
+    assert_eq!(Day11::part_one(&grid), 37);
+    assert_eq!(Day11::part_two(&grid), 26);
+}
+
+#[test]
+fn settles() {
+    let seats = Day11::parse(EXAMPLE).expect("correct parse");
+
+    let settled = simulate(watched_near(&seats), 4, None);
+    assert_eq!((settled.cycle, settled.population), (Some(crate::automaton::Cycle { start: 5, period: 1 }), 37));
+
+    let limited = simulate(watched_near(&seats), 4, Some(2));
+    assert_eq!((limited.generations, limited.cycle), (2, None));
+}
@@ -0,0 +1,170 @@
+//! Day 12: Rain Risk.
+
+use std::f64::consts::PI;
+
+use crate::error::{ParseError, parse_as};
+use crate::solver::Solver;
+
+#[derive(Clone, Copy, Debug)]
+pub enum Action {
+    North,
+    South,
+    East,
+    West,
+    Left,
+    Right,
+    Forward
+}
+
+impl Action {
+    fn from_char(c: char) -> Option<Action> {
+        match c {
+            'N' => Some(Action::North),
+            'S' => Some(Action::South),
+            'E' => Some(Action::East),
+            'W' => Some(Action::West),
+            'L' => Some(Action::Left),
+            'R' => Some(Action::Right),
+            'F' => Some(Action::Forward),
+            _ => None
+        }
+    }
+}
+
+/// Ship position, waypoint relative to the ship and heading in degrees.
+#[derive(Debug)]
+pub struct State {
+    e: f64,
+    n: f64,
+    wpe: f64,
+    wpn: f64,
+    dir: f64
+}
+
+impl State {
+    pub fn new() -> Self {
+        Self {
+            e: 0f64, n: 0f64,
+            wpe: 10f64, wpn: 1f64,
+            dir: 0f64
+        }
+    }
+
+    /// Distance from the start, rounded: turns are whole multiples of 90
+    /// degrees, so it is an integer but for floating point noise.
+    pub fn manhattan_distance(&self) -> i64 {
+        (self.e.abs() + self.n.abs()).round() as i64
+    }
+
+    fn rotate_waypoint(&mut self, degrees: f64) {
+        let radians = degrees * PI / 180.;
+
+        let wpe = self.wpe * radians.cos() - self.wpn * radians.sin();
+        let wpn = self.wpe * radians.sin() + self.wpn * radians.cos();
+
+        self.wpe = wpe;
+        self.wpn = wpn;
+    }
+
+    /// Moves the ship itself.
+    pub fn apply_action(&mut self, action: Action, arg: usize) {
+        let arg = arg as f64;
+
+        match action {
+            Action::North => { self.n += arg; },
+            Action::South => { self.n -= arg; },
+            Action::East => { self.e += arg; },
+            Action::West => { self.e -= arg; },
+            Action::Left => { self.dir += arg; },
+            Action::Right => { self.dir -= arg; },
+            Action::Forward => {
+                let radians = self.dir * PI / 180.;
+                self.e += arg * radians.cos();
+                self.n += arg * radians.sin();
+            }
+        }
+    }
+
+    /// Moves the waypoint, the ship only going forward towards it.
+    pub fn apply_action_waypoint(&mut self, action: Action, arg: usize) {
+        let arg = arg as f64;
+
+        match action {
+            Action::North => { self.wpn += arg; },
+            Action::South => { self.wpn -= arg; },
+            Action::East => { self.wpe += arg; },
+            Action::West => { self.wpe -= arg; },
+            Action::Left => { self.rotate_waypoint(arg); },
+            Action::Right => { self.rotate_waypoint(-arg); },
+            Action::Forward => {
+                self.n += self.wpn * arg;
+                self.e += self.wpe * arg;
+            }
+        }
+    }
+}
+
+impl Default for State {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+fn actions(input: &str) -> Result<Vec<(Action, usize)>, ParseError> {
+    input.lines().map(|s| {
+        let s = s.trim();
+        let (action, arg) = s.split_at(s.chars().next().map_or(0, char::len_utf8));
+        let action = action.chars().next().and_then(Action::from_char)
+            .ok_or_else(|| ParseError::new(action, "an action, one of `NSEWLRF`"))?;
+        let arg = parse_as(arg, "a value")?;
+        Ok((action, arg))
+    }).collect()
+}
+
+pub struct Day12;
+
+impl Solver for Day12 {
+    const DAY: usize = 12;
+
+    type Input = Vec<(Action, usize)>;
+    type One = i64;
+    type Two = i64;
+
+    fn parse(input: &str) -> Result<Vec<(Action, usize)>, ParseError> {
+        actions(input)
+    }
+
+    fn part_one(actions: &Vec<(Action, usize)>) -> i64 {
+        let mut state = State::new();
+
+        for &(action, arg) in actions {
+            state.apply_action(action, arg);
+        }
+
+        state.manhattan_distance()
+    }
+
+    fn part_two(actions: &Vec<(Action, usize)>) -> i64 {
+        let mut state = State::new();
+
+        for &(action, arg) in actions {
+            state.apply_action_waypoint(action, arg);
+        }
+
+        state.manhattan_distance()
+    }
+}
+
+/// Example from the puzzle text.
+pub const EXAMPLE: &str = r"F10
+N3
+F7
+R90
+F11";
+
+#[test]
+fn example() {
+    let actions = Day12::parse(EXAMPLE).expect("correct parse");
+    assert_eq!(Day12::part_one(&actions), 25);
+    assert_eq!(Day12::part_two(&actions), 286);
+}
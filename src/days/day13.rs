@@ -0,0 +1,120 @@
+//! Day 13: Shuttle Search.
+
+use num::integer::Integer;
+use num::bigint::BigInt;
+
+use crate::error::{ParseError, parse_as};
+use crate::solver::Solver;
+
+/// Earliest departure timestamp and the bus periods, `None` for buses out of service.
+#[derive(Debug)]
+pub struct Req {
+    pub earliest: usize,
+    pub periods: Vec<Option<usize>>
+}
+
+fn req(input: &str) -> Result<Req, ParseError> {
+    let mut lines = input.lines();
+
+    let earliest = lines.next().ok_or_else(|| ParseError::end(input, "the earliest timestamp"))?;
+    let periods = lines.next().ok_or_else(|| ParseError::end(input, "the bus periods"))?;
+
+    let earliest = parse_as(earliest, "a timestamp")?;
+    let periods: Result<Vec<Option<usize>>, _> = periods.split(",").map(|part| {
+        let ret = if part == "x" {
+            None
+        } else {
+            Some(parse_as(part, "a bus period or `x`")?)
+        };
+
+        Ok(ret)
+    }).collect();
+
+    let periods = periods?;
+
+    Ok(Req { earliest, periods })
+}
+
+/// Combines `x = r1 (mod m1)` and `x = r2 (mod m2)` into `x = r (mod m1*m2)`
+/// via the Chinese remainder theorem, the moduli being coprime.
+pub fn crt(r1: BigInt, m1: BigInt, r2: BigInt, m2: BigInt) -> (BigInt, BigInt) {
+    let e = BigInt::extended_gcd(&m1, &m2);
+    assert!(e.gcd == 1.into());
+
+    let m = m1.clone() * m2.clone();
+    let r = (r2 * e.x * m1 + r1 * e.y * m2) % m.clone();
+    let r = (r + m.clone()) % m.clone();
+
+    (r, m)
+}
+
+pub struct Day13;
+
+impl Solver for Day13 {
+    const DAY: usize = 13;
+
+    type Input = Req;
+    type One = usize;
+    type Two = BigInt;
+
+    fn parse(input: &str) -> Result<Req, ParseError> {
+        req(input)
+    }
+
+    fn part_one(reqs: &Req) -> usize {
+        let (wait, id) = reqs.periods.iter()
+            .filter_map(|oid| oid.map(|id| (id - reqs.earliest % id, id))).min()
+            .expect("at least one");
+
+        id * wait
+    }
+
+    fn part_two(reqs: &Req) -> BigInt {
+        let gcds: Vec<(BigInt, BigInt)> = reqs.periods.iter().enumerate()
+            .filter_map(|(index, id)| id.map(|id| (BigInt::from(index), id.into())))
+            .collect();
+
+        let (mut r1, mut m1) = gcds.first().expect("at least one").clone();
+
+        for (r2, m2) in gcds[1..].iter() {
+            let (r1_n, m1_n) = crt(r1, m1, -r2.clone(), m2.clone());
+            r1 = r1_n; m1 = m1_n;
+        }
+
+        r1
+    }
+}
+
+/// Example from the puzzle text.
+pub const EXAMPLE: &str = r"939
+7,13,x,x,59,x,31,19";
+
+#[test]
+fn example_1() {
+    let req = Day13::parse(EXAMPLE).expect("correct parse");
+    assert_eq!(Day13::part_one(&req), 295);
+    assert_eq!(Day13::part_two(&req), 1068781.into());
+}
+
+#[test]
+fn example_2() {
+    let input = r"0
+17,x,13,19";
+    assert_eq!(Day13::part_two(&req(input).expect("correct parse")), 3417.into());
+
+    let input = r"0
+67,7,59,61";
+    assert_eq!(Day13::part_two(&req(input).expect("correct parse")), 754018.into());
+
+    let input = r"0
+67,x,7,59,61";
+    assert_eq!(Day13::part_two(&req(input).expect("correct parse")), 779210.into());
+
+    let input = r"0
+67,7,x,59,61";
+    assert_eq!(Day13::part_two(&req(input).expect("correct parse")), 1261476.into());
+
+    let input = r"0
+1789,37,47,1889";
+    assert_eq!(Day13::part_two(&req(input).expect("correct parse")), 1202161486.into());
+}
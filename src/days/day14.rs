@@ -0,0 +1,147 @@
+//! Day 14: Docking Data.
+
+use std::collections::HashMap;
+
+use itertools::iterate;
+use regex::Regex;
+
+use crate::error::{ParseError, parse_as};
+use crate::solver::Solver;
+
+lazy_static! {
+    static ref MASK_OP: Regex = Regex::new(r"^mask = ([01X]{36})$").expect("compiles");
+    static ref MEM_OP: Regex = Regex::new(r"^mem\[(\d+)\] = (\d+)$").expect("compiles");
+}
+
+/// Bitmask split into the floating (`X`) bits and the bits forced to one.
+#[derive(Clone, Copy, Debug)]
+pub struct Masks {
+    pub and_mask: u64,
+    pub or_mask: u64
+}
+
+impl Masks {
+    fn new() -> Self {
+        Self { and_mask: 0, or_mask: 0 }
+    }
+
+    fn append_bits(&self, and_bit: u64, or_bit: u64) -> Self {
+        let and_mask = self.and_mask * 2 + and_bit;
+        let or_mask = self.or_mask * 2 + or_bit;
+        Self { and_mask, or_mask }
+    }
+}
+
+#[derive(Clone, Copy, Debug)]
+pub enum Op {
+    Mask(Masks),
+    Mem { dest: u64, value: u64 }
+}
+
+fn program(input: &str) -> Result<Vec<Op>, ParseError> {
+    input.lines().map(|line| {
+        if let Some(mask) = MASK_OP.captures(line) {
+            let masks = mask[1].chars().fold(
+                Masks::new(),
+                |masks, c| {
+                    let (and_bit, or_bit) = match c {
+                        'X' => (1, 0),
+                        '1' => (0, 1),
+                        '0' => (0, 0),
+                        _ => unreachable!()
+                    };
+
+                    masks.append_bits(and_bit, or_bit)
+                });
+
+            Ok(Op::Mask(masks))
+        } else if let Some(mem) = MEM_OP.captures(line) {
+            let dest = parse_as(&mem[1], "an address")?;
+            let value = parse_as(&mem[2], "a value")?;
+
+            Ok(Op::Mem { dest, value })
+        } else {
+            Err(ParseError::new(line, "`mask = <bits>` or `mem[<address>] = <value>`"))
+        }
+    }).collect()
+}
+
+fn subset_masks(mask: u64) -> impl Iterator<Item=u64> {
+    iterate(mask, move |&m| (m.wrapping_sub(1)) & mask).take_while(|&m| m != 0)
+}
+
+pub struct Day14;
+
+impl Solver for Day14 {
+    const DAY: usize = 14;
+
+    type Input = Vec<Op>;
+    type One = u64;
+    type Two = u64;
+
+    fn parse(input: &str) -> Result<Vec<Op>, ParseError> {
+        program(input)
+    }
+
+    fn part_one(program: &Vec<Op>) -> u64 {
+        let mut memory = HashMap::<u64, u64>::new();
+        let mut last_mask = None;
+
+        for &op in program {
+            match op {
+                Op::Mask(mask) => { last_mask = Some(mask); },
+                Op::Mem { dest, value } => {
+                    let mask = last_mask.expect("must be set");
+                    memory.insert(dest, (value & mask.and_mask) | mask.or_mask);
+                }
+            }
+        }
+
+        memory.values().sum()
+    }
+
+    fn part_two(program: &Vec<Op>) -> u64 {
+        let mut memory = HashMap::<u64, u64>::new();
+        let mut last_mask = None;
+
+        for &op in program {
+            match op {
+                Op::Mask(mask) => { last_mask = Some(mask); },
+                Op::Mem { dest, value } => {
+                    let mask = last_mask.expect("must be set");
+                    let dest = (dest | mask.or_mask) & !mask.and_mask;
+
+                    memory.insert(dest, value);
+
+                    for or_mask in subset_masks(mask.and_mask) {
+                        memory.insert(dest | or_mask, value);
+                    }
+                }
+            }
+        }
+
+        memory.values().sum()
+    }
+}
+
+/// Examples from the puzzle text.
+pub const EXAMPLE_1: &str = r"mask = XXXXXXXXXXXXXXXXXXXXXXXXXXXXX1XXXX0X
+mem[8] = 11
+mem[7] = 101
+mem[8] = 0";
+pub const EXAMPLE_2: &str = r"mask = 000000000000000000000000000000X1001X
+mem[42] = 100
+mask = 00000000000000000000000000000000X0XX
+mem[26] = 1";
+
+#[test]
+fn example_1() {
+    let program = Day14::parse(EXAMPLE_1).expect("correct parse");
+    assert_eq!(Day14::part_one(&program), 165);
+}
+
+#[test]
+fn example_2() {
+    let program = Day14::parse(EXAMPLE_2).expect("correct parse");
+    assert_eq!(Day14::part_two(&program), 208);
+}
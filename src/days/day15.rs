@@ -0,0 +1,80 @@
+//! Day 15: Rambunctious Recitation.
+
+use std::collections::HashMap;
+
+use crate::error::{ParseError, parse_as};
+use crate::solver::Solver;
+
+const P1_CNT: usize = 2020;
+const P2_CNT: usize = 30000000;
+
+fn parse(input: &str) -> Result<Vec<usize>, ParseError> {
+    input.trim().split(',').map(|p| parse_as(p, "a starting number")).collect()
+}
+
+/// The `index`-th number spoken in the memory game, counting from one.
+pub fn part_both(start: &[usize], index: usize) -> usize {
+    let mut memory = start.to_vec();
+    let mut last_pos: HashMap<usize, usize> = memory[..memory.len()-1].iter()
+        .enumerate()
+        .map(|(i, &v)| (v, i))
+        .collect();
+
+    loop {
+        let (&last, head) = memory.split_last().expect("more than one");
+
+        if memory.len() == index {
+            return last;
+        }
+
+        let next = last_pos.insert(last, head.len()).map_or(0, |pos| head.len() - pos);
+        memory.push(next);
+    }
+}
+
+pub struct Day15;
+
+impl Solver for Day15 {
+    const DAY: usize = 15;
+
+    type Input = Vec<usize>;
+    type One = usize;
+    type Two = usize;
+
+    fn parse(input: &str) -> Result<Vec<usize>, ParseError> {
+        parse(input)
+    }
+
+    fn part_one(start: &Vec<usize>) -> usize {
+        part_both(start, P1_CNT)
+    }
+
+    fn part_two(start: &Vec<usize>) -> usize {
+        part_both(start, P2_CNT)
+    }
+}
+
+/// Example from the puzzle text.
+pub const EXAMPLE: &str = "0,3,6";
+
+#[test]
+fn example_1() {
+    assert_eq!(Day15::parse(EXAMPLE).map(|start| Day15::part_one(&start)), Ok(436));
+    assert_eq!(part_both(&[1, 3, 2], P1_CNT), 1);
+    assert_eq!(part_both(&[2, 1, 3], P1_CNT), 10);
+    assert_eq!(part_both(&[1, 2, 3], P1_CNT), 27);
+    assert_eq!(part_both(&[2, 3, 1], P1_CNT), 78);
+    assert_eq!(part_both(&[3, 2, 1], P1_CNT), 438);
+    assert_eq!(part_both(&[3, 1, 2], P1_CNT), 1836);
+}
+
+#[test]
+fn example_2() {
+    assert_eq!(part_both(&[0, 3, 6], P2_CNT), 175594);
+    assert_eq!(part_both(&[1, 3, 2], P2_CNT), 2578);
+    assert_eq!(part_both(&[2, 1, 3], P2_CNT), 3544142);
+    assert_eq!(part_both(&[1, 2, 3], P2_CNT), 261214);
+    assert_eq!(part_both(&[2, 3, 1], P2_CNT), 6895259);
+    assert_eq!(part_both(&[3, 2, 1], P2_CNT), 18);
+    assert_eq!(part_both(&[3, 1, 2], P2_CNT), 362);
+}
@@ -0,0 +1,189 @@
+//! Day 16: Ticket Translation.
+
+use std::collections::HashSet;
+
+use crate::error::{ParseError, parse_as};
+use crate::solver::Solver;
+
+#[derive(Debug)]
+pub struct Range {
+    pub low: usize,
+    pub high: usize
+}
+
+impl Range {
+    pub fn within(&self, n: usize) -> bool {
+        self.low <= n && n <= self.high
+    }
+}
+
+pub type Ticket = Vec<usize>;
+
+/// Rule name and its index in the rule list.
+pub type RuleRef = (String, usize);
+
+/// Ticket field rules, your ticket and the nearby tickets.
+#[derive(Debug)]
+pub struct Stmt {
+    pub rules: Vec<(String, Vec<Range>)>,
+    pub your: Ticket,
+    pub nearby: Vec<Ticket>
+}
+
+impl Stmt {
+    /// The value is not valid for any field.
+    pub fn invalid_all(&self, n: usize) -> bool {
+        self.rules.iter().all(|(_, rs)| rs.iter().all(|r| !r.within(n)))
+    }
+
+    /// For every column, the rules all `tickets` satisfy there.
+    pub fn eligible(&self, tickets: &[Ticket]) -> Vec<Vec<RuleRef>> {
+        (0..tickets.first().map_or(0, |t| t.len())).map(|col| {
+            self.rules.iter().enumerate().filter_map(|(i, (name, rs))| {
+                if tickets.iter().all(|t| rs.iter().any(|r| r.within(t[col]))) {
+                    Some((name.clone(), i))
+                } else {
+                    None
+                }
+            }).collect()
+        }).collect()
+    }
+}
+
+fn parse_ticket(line: &str) -> Result<Vec<usize>, ParseError> {
+    line.split(",").map(|s| parse_as(s, "a field value")).collect()
+}
+
+fn parse(input: &str) -> Result<Stmt, ParseError> {
+    let lines: Vec<&str> = input.lines().map(str::trim).collect();
+
+    let mut parts = lines.split(|line| line.is_empty());
+
+    let rules = parts.next().ok_or_else(|| ParseError::end(input, "ticket rules"))?.iter().map(|line| {
+        let (name, ranges) = line.split_once(": ").ok_or_else(|| ParseError::new(line, "`<field>: <ranges>`"))?;
+
+        let ranges: Result<Vec<Range>, _> = ranges.split(" or ").map(|range| {
+            let (low, high) = range.split_once("-").ok_or_else(|| ParseError::new(range, "a range like `1-3`"))?;
+
+            let low = parse_as(low, "a number")?;
+            let high = parse_as(high, "a number")?;
+
+            Ok(Range { low, high })
+        }).collect();
+
+        Ok((name.to_string(), ranges?))
+    }).collect::<Result<Vec<(String, Vec<Range>)>, ParseError>>()?;
+
+    let your = parts.next().and_then(|p| p.get(1)).ok_or_else(|| ParseError::end(input, "your ticket"))?;
+    let your = parse_ticket(your)?;
+
+    let nearby = parts.next().and_then(|p| p.get(1..)).ok_or_else(|| ParseError::end(input, "nearby tickets"))?;
+    let nearby = nearby.iter().map(|s| parse_ticket(s)).collect::<Result<Vec<Vec<usize>>, ParseError>>()?;
+
+    Ok(Stmt { rules, your, nearby })
+}
+
+/// Assigns a distinct rule to every column out of the eligible ones.
+pub fn tsp(columns: &[Vec<RuleRef>]) -> Option<Vec<RuleRef>> {
+    fn tsp_rec(
+        used_rules: &mut HashSet<usize>,
+        path: &mut Vec<RuleRef>,
+        columns: &[Vec<RuleRef>]) -> Result<(), Vec<RuleRef>>
+    {
+        if let Some((rule_refs, columns)) = columns.split_last() {
+            for (name, rule_idx) in rule_refs.iter() {
+                if used_rules.insert(*rule_idx) {
+                    path.push((name.clone(), *rule_idx));
+                    tsp_rec(used_rules, path, columns)?;
+                    path.pop();
+                    used_rules.remove(rule_idx);
+                }
+            }
+            Ok(())
+        } else {
+            path.reverse();
+            Err(path.clone())
+        }
+    }
+
+    tsp_rec(&mut HashSet::new(), &mut Vec::new(), columns).err()
+}
+
+pub struct Day16;
+
+impl Solver for Day16 {
+    const DAY: usize = 16;
+
+    type Input = Stmt;
+    type One = usize;
+    type Two = usize;
+
+    fn parse(input: &str) -> Result<Stmt, ParseError> {
+        parse(input)
+    }
+
+    fn part_one(stmt: &Stmt) -> usize {
+        stmt.nearby.iter().flatten().filter(|&&n| stmt.invalid_all(n)).sum()
+    }
+
+    fn part_two(stmt: &Stmt) -> usize {
+        let correct: Vec<Vec<usize>> = stmt.nearby.iter()
+            .filter(|&nt| nt.iter().all(|&n| !stmt.invalid_all(n)))
+            .cloned()
+            .collect();
+
+        let eligible = stmt.eligible(&correct);
+        let path = tsp(&eligible).expect("exists");
+
+        let ans = path.into_iter()
+            .map(|(name, _)| name)
+            .zip(stmt.your.iter())
+            .filter(|(name, _)| name.starts_with("departure"))
+            .map(|(_, &value)| value)
+            .product();
+
+        ans
+    }
+}
+
+/// Examples from the puzzle text.
+pub const EXAMPLE_1: &str = r"class: 1-3 or 5-7
+row: 6-11 or 33-44
+seat: 13-40 or 45-50
+
+your ticket:
+7,1,14
+
+nearby tickets:
+7,3,47
+40,4,50
+55,2,20
+38,6,12
+";
+pub const EXAMPLE_2: &str = r"class: 0-1 or 4-19
+row: 0-5 or 8-19
+seat: 0-13 or 16-19
+
+your ticket:
+11,12,13
+
+nearby tickets:
+3,9,18
+15,1,5
+5,14,9";
+
+#[test]
+fn example_1() {
+    let stmt = Day16::parse(EXAMPLE_1).expect("correct parse");
+    assert_eq!(Day16::part_one(&stmt), 71);
+}
+
+#[test]
+fn example_2() {
+    let stmt = Day16::parse(EXAMPLE_2).expect("correct parse");
+    let path = tsp(&stmt.eligible(&stmt.nearby)).expect("exists");
+    let names: Vec<&str> = path.iter().map(|(name, _)| name.as_str()).collect();
+
+    assert_eq!(names, ["row", "class", "seat"]);
+    assert_eq!(Day16::part_two(&stmt), 1);
+}
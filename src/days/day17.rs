@@ -0,0 +1,70 @@
+//! Day 17: Conway Cubes.
+
+use std::collections::BTreeSet;
+
+use crate::automaton::{Automaton, Cubic, Point};
+use crate::error::ParseError;
+use crate::grid::{Grid, pixel};
+use crate::solver::Solver;
+
+const CYCLES: usize = 6;
+
+fn parse(input: &str) -> Result<Vec<Point>, ParseError> {
+    let slice = Grid::parse(input, "`.` or `#`", pixel)?;
+
+    let pts = slice.positions()
+        .filter(|&pos| slice[pos])
+        .map(|(x, y)| Point::from_slice(&[x as isize, y as isize]))
+        .collect();
+
+    Ok(pts)
+}
+
+/// Active cubes after six cycles, starting from the active cells of a
+/// planar `slice` embedded into `dims` dimensions.
+pub fn part_both(slice: &[Point], dims: usize) -> usize {
+    let points: BTreeSet<Point> = slice.iter()
+        .map(|pt| {
+            let mut pt = pt.clone();
+            pt.resize(dims, 0);
+            pt
+        })
+        .collect();
+
+    let conway = Automaton::new(Cubic::new(dims), "B3/S23".parse().expect("valid rule"));
+    conway.population(&conway.run(&points, CYCLES))
+}
+
+pub struct Day17;
+
+impl Solver for Day17 {
+    const DAY: usize = 17;
+
+    type Input = Vec<Point>;
+    type One = usize;
+    type Two = usize;
+
+    fn parse(input: &str) -> Result<Vec<Point>, ParseError> {
+        parse(input)
+    }
+
+    fn part_one(slice: &Vec<Point>) -> usize {
+        part_both(slice, 3)
+    }
+
+    fn part_two(slice: &Vec<Point>) -> usize {
+        part_both(slice, 4)
+    }
+}
+
+/// Example from the puzzle text.
+pub const EXAMPLE: &str = r".#.
+..#
+###";
+
+#[test]
+fn example() {
+    let slice = Day17::parse(EXAMPLE).expect("correct parse");
+    assert_eq!(part_both(&slice, 3), 112);
+    assert_eq!(part_both(&slice, 4), 848);
+}
@@ -0,0 +1,191 @@
+//! Day 18: Operation Order.
+
+use crate::error::{ParseError, parse_as};
+use crate::solver::Solver;
+
+#[derive(Eq, PartialEq, Copy, Clone, Debug)]
+enum Op {
+    Add,
+    Mul
+}
+
+type Parsed<'a, T> = Result<(T, &'a str), ParseError>;
+
+fn parse_number(input: &str) -> Parsed<'_, usize> {
+    let last_digit_pos = input.find(|c: char| !c.is_ascii_digit()).unwrap_or(input.len());
+    let (number, tail) = input.split_at(last_digit_pos);
+
+    if number.is_empty() {
+        return Err(ParseError::new(input, "a number or `(`"));
+    }
+
+    Ok((parse_as(number, "a number")?, tail))
+}
+
+fn parse_parexp(input: &str) -> Parsed<'_, usize> {
+    let (number, tail) = parse_lassoc(&input[1..])?;
+
+    let tail = tail.trim_start();
+    let tail = tail.strip_prefix(')').ok_or_else(|| ParseError::new(tail, "an operator or `)`"))?;
+
+    Ok((number, tail))
+}
+
+fn parse_operand(input: &str) -> Parsed<'_, usize> {
+    let input = input.trim_start();
+    if input.starts_with('(') { parse_parexp(input) } else { parse_number(input) }
+}
+
+fn parse_parexp_advanced(input: &str) -> Parsed<'_, usize> {
+    let (number, tail) = parse_mul(&input[1..])?;
+
+    let tail = tail.trim_start();
+    let tail = tail.strip_prefix(')').ok_or_else(|| ParseError::new(tail, "an operator or `)`"))?;
+
+    Ok((number, tail))
+}
+
+fn parse_operand_advanced(input: &str) -> Parsed<'_, usize> {
+    let input = input.trim_start();
+    if input.starts_with('(') { parse_parexp_advanced(input) } else { parse_number(input) }
+}
+
+fn parse_op(input: &str) -> Option<(Op, &str)> {
+    let input = input.trim_start();
+    let op = match input.chars().next()? {
+        '+' => Op::Add,
+        '*' => Op::Mul,
+        _ => { return None; }
+    };
+
+    Some((op, input.get(1..)?))
+}
+
+fn parse_lassoc(input: &str) -> Parsed<'_, usize> {
+    let (mut cur, mut tail) = parse_operand(input)?;
+
+    while let Some((op, op_tail)) = parse_op(tail) {
+        let (number, ntail) = parse_operand(op_tail)?;
+
+        match op {
+            Op::Add => { cur += number; },
+            Op::Mul => { cur *= number; }
+        }
+
+        tail = ntail;
+    }
+
+    Ok((cur, tail))
+}
+
+fn parse_add(input: &str) -> Parsed<'_, usize> {
+    let (lhs, tail) = parse_operand_advanced(input)?;
+
+    match parse_op(tail) {
+        Some((Op::Add, tail)) => {
+            let (rhs, tail) = parse_add(tail)?;
+            Ok((lhs + rhs, tail))
+        },
+
+        _ => Ok((lhs, tail))
+    }
+}
+
+fn parse_mul(input: &str) -> Parsed<'_, usize> {
+    let (lhs, tail) = parse_add(input)?;
+
+    match parse_op(tail) {
+        Some((Op::Mul, tail)) => {
+            let (rhs, tail) = parse_mul(tail)?;
+            Ok((lhs * rhs, tail))
+        },
+
+        _ => Ok((lhs, tail))
+    }
+}
+
+fn whole_line(parsed: Parsed<'_, usize>) -> Result<usize, ParseError> {
+    let (val, tail) = parsed?;
+    let tail = tail.trim();
+
+    if tail.is_empty() {
+        Ok(val)
+    } else {
+        Err(ParseError::new(tail, "an operator or the end of the line"))
+    }
+}
+
+/// Evaluates left to right, `+` and `*` having the same precedence.
+pub fn eval_line_simple(line: &str) -> Result<usize, ParseError> {
+    whole_line(parse_lassoc(line))
+}
+
+/// Evaluates with `+` binding tighter than `*`.
+pub fn eval_line_advanced(line: &str) -> Result<usize, ParseError> {
+    whole_line(parse_mul(line))
+}
+
+pub struct Day18;
+
+impl Solver for Day18 {
+    const DAY: usize = 18;
+
+    type Input = Vec<String>;
+    type One = usize;
+    type Two = usize;
+
+    fn parse(input: &str) -> Result<Vec<String>, ParseError> {
+        input.lines()
+            .map(|line| eval_line_simple(line).map(|_| line.to_string()))
+            .collect()
+    }
+
+    fn part_one(lines: &Vec<String>) -> usize {
+        lines.iter().map(|l| eval_line_simple(l).expect("checked by parse")).sum()
+    }
+
+    fn part_two(lines: &Vec<String>) -> usize {
+        lines.iter().map(|l| eval_line_advanced(l).expect("checked by parse")).sum()
+    }
+}
+
+/// Examples from the puzzle text, one per line.
+pub const EXAMPLE: &str = r"1 + 2 * 3 + 4 * 5 + 6
+1 + (2 * 3) + (4 * (5 + 6))
+2 * 3 + (4 * 5)
+5 + (8 * 3 + 9 + 3 * 4 * 3)
+5 * 9 * (7 * 3 * 3 + 9 * 3 + (8 + 6 * 4))
+((2 + 4 * 9) * (6 + 9 * 8 + 6) + 6) + 2 + 4 * 2";
+
+#[test]
+fn example() {
+    let lines = Day18::parse(EXAMPLE).expect("correct parse");
+    assert_eq!(Day18::part_one(&lines), 26457);
+    assert_eq!(Day18::part_two(&lines), 694173);
+}
+
+#[test]
+fn example_1() {
+    assert_eq!(eval_line_simple("2 * 3 + (4 * 5)"), Ok(26));
+    assert_eq!(eval_line_simple("5 + (8 * 3 + 9 + 3 * 4 * 3)"), Ok(437));
+    assert_eq!(eval_line_simple("5 * 9 * (7 * 3 * 3 + 9 * 3 + (8 + 6 * 4))"), Ok(12240));
+    assert_eq!(eval_line_simple("((2 + 4 * 9) * (6 + 9 * 8 + 6) + 6) + 2 + 4 * 2"), Ok(13632));
+}
+
+#[test]
+fn example_2() {
+    assert_eq!(eval_line_advanced("1 + 2 * 3 + 4 * 5 + 6"), Ok(231));
+    assert_eq!(eval_line_advanced("1 + (2 * 3) + (4 * (5 + 6))"), Ok(51));
+    assert_eq!(eval_line_advanced("2 * 3 + (4 * 5)"), Ok(46));
+    assert_eq!(eval_line_advanced("5 * 9 * (7 * 3 * 3 + 9 * 3 + (8 + 6 * 4))"), Ok(669060));
+    assert_eq!(eval_line_advanced("((2 + 4 * 9) * (6 + 9 * 8 + 6) + 6) + 2 + 4 * 2"), Ok(23340));
+}
+
+#[test]
+fn malformed() {
+    let err = eval_line_simple("1 + (2 * 3").unwrap_err();
+    assert_eq!((err.text.as_str(), err.expected.as_str()), ("", "an operator or `)`"));
+
+    let err = eval_line_advanced("1 + 2) * 3").unwrap_err();
+    assert_eq!((err.text.as_str(), err.expected.as_str()), (") * 3", "an operator or the end of the line"));
+}
@@ -0,0 +1,207 @@
+//! Day 19: Monster Messages.
+
+use std::collections::HashMap;
+
+use crate::error::{ParseError, parse_as};
+use crate::solver::Solver;
+
+const MAX_REPEAT: usize = 6;
+
+/// A grammar rule: a literal letter or alternatives of rule sequences.
+#[derive(Debug)]
+pub enum Rule {
+    Letter(char),
+    Recurse(Vec<Vec<usize>>)
+}
+
+pub type Rules = HashMap<usize, Rule>;
+
+/// The rules together with the received messages.
+#[derive(Debug)]
+pub struct Stmt {
+    pub rules: Rules,
+    pub messages: Vec<String>
+}
+
+impl Stmt {
+    /// Whether `msg` matches rule 0, with rules 8 and 11 looping if `alt_rules`.
+    pub fn is_correct(&self, msg: &str, alt_rules: bool) -> bool {
+        fn rec<'a>(rules: &'a Rules, index: usize, msg: &'a str) -> Option<&'a str>
+        {
+            let rule = rules.get(&index)?;
+
+            match rule {
+                &Rule::Letter(first_char) => {
+                    if msg.chars().next()? != first_char {
+                        None
+                    } else {
+                        Some(msg.get(1..)?)
+                    }
+                },
+
+                Rule::Recurse(options) => {
+                    options.iter().filter_map(|option| {
+                        let mut msg = msg;
+                        for &index in option {
+                            msg = rec(rules, index, msg)?;
+                        }
+
+                        Some(msg)
+                    }).next()
+                }
+            }
+        }
+
+        if !alt_rules {
+            rec(&self.rules, 0, msg).map(str::is_empty).unwrap_or(false)
+
+        } else {
+            let counts = (2..=MAX_REPEAT).flat_map(|n| (1..n).rev().map(move |m| (n, m)));
+            let mut leftovers = counts.filter_map(|(n, m)| {
+                let mut msg = msg;
+                for _ in 0..n { msg = rec(&self.rules, 42, msg)?; }
+                for _ in 0..m { msg = rec(&self.rules, 31, msg)?; }
+                Some(msg)
+            });
+
+            leftovers.any(str::is_empty)
+        }
+    }
+}
+
+fn parse_rule(line: &str) -> Result<(usize, Rule), ParseError> {
+    let (index, rule) = line.split_once(": ").ok_or_else(|| ParseError::new(line, "`<index>: <rule>`"))?;
+
+    let index = parse_as(index.trim(), "a rule index")?;
+
+    let rule = rule.trim();
+    let rule = if rule.contains('"') {
+        Rule::Letter(rule.chars().nth(1).ok_or_else(|| ParseError::new(rule, "a quoted letter"))?)
+
+    } else {
+        let options = rule.split("|").map(|part| {
+            part.split_whitespace()
+                .map(|i| parse_as(i, "a rule index"))
+                .collect::<Result<_, _>>()
+        }).collect::<Result<_, _>>();
+
+        Rule::Recurse(options?)
+    };
+
+    Ok((index, rule))
+}
+
+fn stmt(input: &str) -> Result<Stmt, ParseError> {
+    let lines: Vec<&str> = input.lines().map(str::trim).collect();
+
+    let mut parts = lines.split(|l| l.is_empty());
+
+    let rules: Result<_, _> = parts.next().ok_or_else(|| ParseError::end(input, "rules"))?.iter().map(|l| parse_rule(l)).collect();
+    let rules = rules?;
+
+    let messages = parts.next().ok_or_else(|| ParseError::end(input, "messages after a blank line"))?;
+    let messages = messages.iter().map(|l| l.to_string()).collect();
+
+    Ok(Stmt{ rules, messages })
+}
+
+/// Number of messages matching rule 0.
+pub fn part_both(stmt: &Stmt, alt_rules: bool) -> usize {
+    stmt.messages.iter().filter(|s| stmt.is_correct(s, alt_rules)).count()
+}
+
+pub struct Day19;
+
+impl Solver for Day19 {
+    const DAY: usize = 19;
+
+    type Input = Stmt;
+    type One = usize;
+    type Two = usize;
+
+    fn parse(input: &str) -> Result<Stmt, ParseError> {
+        stmt(input)
+    }
+
+    fn part_one(stmt: &Stmt) -> usize {
+        part_both(stmt, false)
+    }
+
+    fn part_two(stmt: &Stmt) -> usize {
+        part_both(stmt, true)
+    }
+}
+
+/// Examples from the puzzle text.
+pub const EXAMPLE_1: &str = r#"0: 4 1 5
+1: 2 3 | 3 2
+2: 4 4 | 5 5
+3: 4 5 | 5 4
+4: "a"
+5: "b"
+
+ababbb
+bababa
+abbbab
+aaabbb
+aaaabbb"#;
+pub const EXAMPLE_2: &str = r#"42: 9 14 | 10 1
+9: 14 27 | 1 26
+10: 23 14 | 28 1
+1: "a"
+11: 42 31
+5: 1 14 | 15 1
+19: 14 1 | 14 14
+12: 24 14 | 19 1
+16: 15 1 | 14 14
+31: 14 17 | 1 13
+6: 14 14 | 1 14
+2: 1 24 | 14 4
+0: 8 11
+13: 14 3 | 1 12
+15: 1 | 14
+17: 14 2 | 1 7
+23: 25 1 | 22 14
+28: 16 1
+4: 1 1
+20: 14 14 | 1 15
+3: 5 14 | 16 1
+27: 1 6 | 14 18
+14: "b"
+21: 14 1 | 1 14
+25: 1 1 | 1 14
+22: 14 14
+8: 42
+26: 14 22 | 1 20
+18: 15 15
+7: 14 5 | 1 21
+24: 14 1
+
+abbbbbabbbaaaababbaabbbbabababbbabbbbbbabaaaa
+bbabbbbaabaabba
+babbbbaabbbbbabbbbbbaabaaabaaa
+aaabbbbbbaaaabaababaabababbabaaabbababababaaa
+bbbbbbbaaaabbbbaaabbabaaa
+bbbababbbbaaaaaaaabbababaaababaabab
+ababaaaaaabaaab
+ababaaaaabbbaba
+baabbaaaabbaaaababbaababb
+abbbbabbbbaaaababbbbbbaaaababb
+aaaaabbaabaaaaababaa
+aaaabbaaaabbaaa
+aaaabbaabbaaaaaaabbbabbbaaabbaabaaa
+babaaabbbaaabaababbaabababaaab
+aabbbbbaabbbaaaaaabbbbbababaaaaabbaaabba"#;
+
+#[test]
+fn example_1() {
+    let stmt = Day19::parse(EXAMPLE_1).expect("correct parse");
+    assert_eq!(Day19::part_one(&stmt), 2);
+}
+
+#[test]
+fn example_2() {
+    let stmt = Day19::parse(EXAMPLE_2).expect("correct parse");
+    assert_eq!(Day19::part_one(&stmt), 3);
+    assert_eq!(Day19::part_two(&stmt), 12);
+}
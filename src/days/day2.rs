@@ -0,0 +1,531 @@
+//! Day 2: Password Philosophy.
+//!
+//! Each line is a policy and a password, `policy: password`. A policy is one
+//! or more rules separated by `, `, all of which have to hold. A rule is two
+//! numbers, how they are checked and what they are about:
+//!
+//! - `1-3 a`: between 1 and 3 occurrences, as in the puzzle;
+//! - `1^3 a`: exactly one of the 1-based positions 1 and 3 holds it;
+//! - `1&3 a`: both positions do;
+//! - `1|3 a`: at least one of them does.
+//!
+//! A rule is about a single letter, a class of characters in brackets like
+//! `[a-f]` or `[^aeiou]`, or several letters in a row like `ab`, which is
+//! counted wherever it starts and checked at the positions it starts at.
+
+use std::fmt;
+use std::io::BufRead;
+use std::str::FromStr;
+use regex::Regex;
+
+use crate::Part;
+use crate::error::{ParseError, parse_as};
+use crate::report::{Format, json_string};
+use crate::solver::{self, Answer, Solver};
+use crate::stream::{self, StreamError};
+
+lazy_static! {
+    static ref RULE: Regex = Regex::new(r"^(\d+)([-^&|])(\d+) (\S+)$").expect("compiles");
+}
+
+/// What a rule is about.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum Letters {
+    One(char),
+    /// Any character in one of the inclusive ranges, or in none of them.
+    Class { negated: bool, ranges: Vec<(char, char)> },
+    Word(String)
+}
+
+impl Letters {
+    /// Whether `password` has these letters starting at 0-based `pos`.
+    fn at(&self, password: &[char], pos: usize) -> bool {
+        match self {
+            Letters::One(letter) => password.get(pos) == Some(letter),
+            Letters::Class { negated, ranges } => password.get(pos).is_some_and(|c| {
+                ranges.iter().any(|&(from, to)| (from..=to).contains(c)) != *negated
+            }),
+            Letters::Word(word) => word.chars().zip(pos..).all(|(w, p)| password.get(p) == Some(&w))
+        }
+    }
+
+    /// Occurrences in `password`, overlapping ones included.
+    fn count(&self, password: &[char]) -> usize {
+        (0..password.len()).filter(|&pos| self.at(password, pos)).count()
+    }
+}
+
+fn class(text: &str) -> Result<Letters, ParseError> {
+    let inner = text.strip_prefix('[').and_then(|t| t.strip_suffix(']'))
+        .ok_or_else(|| ParseError::new(text, "a class like `[a-f]`"))?;
+    let (negated, inner) = match inner.strip_prefix('^') {
+        Some(rest) => (true, rest),
+        None => (false, inner)
+    };
+
+    let chars: Vec<char> = inner.chars().collect();
+    let mut ranges = Vec::new();
+    let mut i = 0;
+
+    while i < chars.len() {
+        if chars.get(i + 1) == Some(&'-') && i + 2 < chars.len() {
+            ranges.push((chars[i], chars[i + 2]));
+            i += 3;
+        } else {
+            ranges.push((chars[i], chars[i]));
+            i += 1;
+        }
+    }
+
+    if ranges.is_empty() {
+        return Err(ParseError::new(text, "a class with at least one character"));
+    }
+
+    Ok(Letters::Class { negated, ranges })
+}
+
+impl FromStr for Letters {
+    type Err = ParseError;
+
+    fn from_str(s: &str) -> Result<Self, ParseError> {
+        if s.starts_with('[') {
+            return class(s);
+        }
+
+        if let Some((pos, c)) = s.char_indices().find(|&(_, c)| !c.is_alphanumeric()) {
+            return Err(ParseError::new(&s[pos..pos + c.len_utf8()], "a letter, a class or a word"));
+        }
+
+        let mut chars = s.chars();
+        match (chars.next(), chars.next()) {
+            (Some(c), None) => Ok(Letters::One(c)),
+            (Some(_), Some(_)) => Ok(Letters::Word(s.to_string())),
+            (None, _) => Err(ParseError::end(s, "a letter, a class or a word"))
+        }
+    }
+}
+
+impl fmt::Display for Letters {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Letters::One(c) => write!(f, "{}", c),
+            Letters::Word(word) => f.write_str(word),
+            Letters::Class { negated, ranges } => {
+                f.write_str(if *negated { "[^" } else { "[" })?;
+
+                for &(from, to) in ranges {
+                    if from == to {
+                        write!(f, "{}", from)?;
+                    } else {
+                        write!(f, "{}-{}", from, to)?;
+                    }
+                }
+
+                f.write_str("]")
+            }
+        }
+    }
+}
+
+/// How a rule reads its two numbers.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum Check {
+    /// As a range of occurrences.
+    Count,
+    /// As positions, exactly one of which must match.
+    Xor,
+    /// As positions, both of which must match.
+    And,
+    /// As positions, at least one of which must match.
+    Or
+}
+
+impl Check {
+    fn symbol(self) -> char {
+        match self {
+            Check::Count => '-',
+            Check::Xor => '^',
+            Check::And => '&',
+            Check::Or => '|'
+        }
+    }
+}
+
+/// Password policy as written in the corporate database: two numbers and
+/// what they are about.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Rule {
+    pub low: usize,
+    pub high: usize,
+    pub letters: Letters,
+    pub check: Check
+}
+
+/// What a rule looked at in a password.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum Observed {
+    /// How often the letters occur.
+    Count(usize),
+    /// The characters at the two positions, if the password is that long, and
+    /// whether the letters start there.
+    Positions { found: [Option<char>; 2], matched: [bool; 2] }
+}
+
+impl fmt::Display for Observed {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Observed::Count(n) => write!(f, "{} occurrence{}", n, if *n == 1 { "" } else { "s" }),
+            Observed::Positions { found, .. } => {
+                let shown = |c: Option<char>| c.map_or("nothing".to_string(), |c| format!("`{}`", c));
+                write!(f, "{} and {}", shown(found[0]), shown(found[1]))
+            }
+        }
+    }
+}
+
+impl Rule {
+    /// What `check` looks at in `password`.
+    pub fn observe(&self, check: Check, password: &str) -> Observed {
+        let password: Vec<char> = password.chars().collect();
+
+        if check == Check::Count {
+            return Observed::Count(self.letters.count(&password));
+        }
+
+        let found = |pos: usize| pos.checked_sub(1).and_then(|pos| password.get(pos).copied());
+        let matched = |pos: usize| pos.checked_sub(1).is_some_and(|pos| self.letters.at(&password, pos));
+
+        Observed::Positions {
+            found: [found(self.low), found(self.high)],
+            matched: [matched(self.low), matched(self.high)]
+        }
+    }
+
+    /// Whether `observed` passes `check` with this rule's numbers.
+    pub fn passes(&self, check: Check, observed: &Observed) -> bool {
+        match (check, observed) {
+            (Check::Count, &Observed::Count(n)) => (self.low..=self.high).contains(&n),
+            // A password too short for either position never passes.
+            (Check::Xor, Observed::Positions { found: [Some(_), Some(_)], matched: [l, h] }) => l ^ h,
+            (Check::And, Observed::Positions { matched: [l, h], .. }) => *l && *h,
+            (Check::Or, Observed::Positions { matched: [l, h], .. }) => *l || *h,
+            _ => false
+        }
+    }
+
+    /// Whether `password` passes this rule, its numbers read as `check` says.
+    pub fn holds(&self, check: Check, password: &str) -> bool {
+        self.passes(check, &self.observe(check, password))
+    }
+
+    /// Whether `password` passes this rule as written.
+    pub fn accepts(&self, password: &str) -> bool {
+        self.holds(self.check, password)
+    }
+
+    /// The letters occur between `low` and `high` times.
+    pub fn is_valid_password_part_one(&self, password: &str) -> bool {
+        self.holds(Check::Count, password)
+    }
+
+    /// Exactly one of the 1-based positions `low` and `high` holds the
+    /// letters, the password being long enough for both.
+    pub fn is_valid_password_part_two(&self, password: &str) -> bool {
+        self.holds(Check::Xor, password)
+    }
+}
+
+impl FromStr for Rule {
+    type Err = ParseError;
+
+    fn from_str(s: &str) -> Result<Self, ParseError> {
+        let c = RULE.captures(s).ok_or_else(|| ParseError::new(s, "a rule like `1-3 a`"))?;
+
+        let check = match &c[2] {
+            "-" => Check::Count,
+            "^" => Check::Xor,
+            "&" => Check::And,
+            _ => Check::Or
+        };
+
+        let position = |text: &str| -> Result<usize, ParseError> {
+            match parse_as(text, "a count")? {
+                0 if check != Check::Count => Err(ParseError::new(text, "a position from 1")),
+                n => Ok(n)
+            }
+        };
+
+        let low = position(c.get(1).expect("matched").as_str())?;
+        let high = position(c.get(3).expect("matched").as_str())?;
+        let letters = c.get(4).expect("matched").as_str().parse()?;
+
+        Ok(Rule { low, high, letters, check })
+    }
+}
+
+impl fmt::Display for Rule {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}{}{} {}", self.low, self.check.symbol(), self.high, self.letters)
+    }
+}
+
+/// Rules a password has to pass all of.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Policy {
+    pub rules: Vec<Rule>
+}
+
+impl Policy {
+    pub fn accepts(&self, password: &str) -> bool {
+        self.rules.iter().all(|rule| rule.accepts(password))
+    }
+}
+
+impl FromStr for Policy {
+    type Err = ParseError;
+
+    fn from_str(s: &str) -> Result<Self, ParseError> {
+        let rules = s.split(", ").map(str::parse).collect::<Result<_, _>>()?;
+        Ok(Policy { rules })
+    }
+}
+
+impl fmt::Display for Policy {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for (i, rule) in self.rules.iter().enumerate() {
+            if i > 0 {
+                f.write_str(", ")?;
+            }
+
+            write!(f, "{}", rule)?;
+        }
+
+        Ok(())
+    }
+}
+
+fn pair(line: &str) -> Result<(Policy, String), ParseError> {
+    let (policy, password) = line.rsplit_once(": ")
+        .ok_or_else(|| ParseError::new(line, "a policy and password like `1-3 a: abcde`"))?;
+
+    if password.is_empty() || password.contains(char::is_whitespace) {
+        return Err(ParseError::new(password, "a password without spaces"));
+    }
+
+    Ok((policy.parse()?, password.to_string()))
+}
+
+fn pairs(input: &str) -> Result<Vec<(Policy, String)>, ParseError> {
+    input.lines().map(pair).collect()
+}
+
+fn valid_passwords<F>(pairs: &[(Policy, String)], f: F) -> usize where F: Fn(&Policy, &str) -> bool {
+    pairs.iter()
+        .filter(|(policy, password)| f(policy, password))
+        .count()
+}
+
+/// Counts the passwords `f` accepts, reading them one per line.
+pub fn valid_passwords_stream<F>(reader: impl BufRead, f: F) -> Result<usize, StreamError> where F: Fn(&Policy, &str) -> bool {
+    let mut valid = 0;
+
+    for pair in stream::parse_lines(Day2::DAY, reader, pair) {
+        let (policy, password) = pair?;
+        valid += f(&policy, &password) as usize;
+    }
+
+    Ok(valid)
+}
+
+/// A rule a password broke.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Violation {
+    /// 1-based line of the password.
+    pub line: usize,
+    pub password: String,
+    /// The rule, as it was checked.
+    pub rule: Rule,
+    pub observed: Observed
+}
+
+impl Violation {
+    /// How the password would have to change to pass.
+    pub fn fix(&self) -> String {
+        let Rule { low, high, ref letters, check } = self.rule;
+
+        match (check, &self.observed) {
+            (Check::Count, &Observed::Count(n)) if n < low => format!("needs {} more `{}`", low - n, letters),
+            (Check::Count, &Observed::Count(n)) => format!("needs {} fewer `{}`", n - high, letters),
+            (Check::Xor, Observed::Positions { found, .. }) if found.contains(&None) => {
+                format!("needs at least {} characters", low.max(high))
+            },
+            (Check::Xor, Observed::Positions { matched: [true, true], .. }) => {
+                format!("`{}` must not be at both {} and {}", letters, low, high)
+            },
+            (Check::And, Observed::Positions { matched, .. }) => {
+                let missing: Vec<_> = [low, high].iter().zip(matched).filter(|(_, &m)| !m).map(|(p, _)| p.to_string()).collect();
+                format!("`{}` must also be at {}", letters, missing.join(" and "))
+            },
+            _ => format!("`{}` must be at {} or {}", letters, low, high)
+        }
+    }
+}
+
+impl fmt::Display for Violation {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "line {}: {} breaks {}: found {}, {}", self.line, self.password, self.rule, self.observed, self.fix())
+    }
+}
+
+/// Every rule broken in `pairs`, by line; part one checks the rules as
+/// written, part two as positions exactly one of which matches.
+pub fn violations(pairs: &[(Policy, String)], part: Part) -> Vec<Violation> {
+    let mut found = Vec::new();
+
+    for ((policy, password), line) in pairs.iter().zip(1..) {
+        for rule in policy.rules.iter() {
+            let check = if part == Part::One { rule.check } else { Check::Xor };
+            let observed = rule.observe(check, password);
+
+            if !rule.passes(check, &observed) {
+                let rule = Rule { check, ..rule.clone() };
+                found.push(Violation { line, password: password.clone(), rule, observed });
+            }
+        }
+    }
+
+    found
+}
+
+fn json_violation(v: &Violation) -> String {
+    let observed = match &v.observed {
+        Observed::Count(n) => format!(r#"{{"count": {}}}"#, n),
+        Observed::Positions { found, matched } => {
+            let at = |i: usize, pos: usize| format!(
+                r#"{{"position": {}, "found": {}, "matches": {}}}"#,
+                pos, found[i].map_or("null".to_string(), |c| json_string(&c.to_string())), matched[i]
+            );
+            format!(r#"{{"positions": [{}, {}]}}"#, at(0, v.rule.low), at(1, v.rule.high))
+        }
+    };
+
+    format!(
+        r#"{{"line": {}, "password": {}, "rule": {}, "observed": {}, "fix": {}}}"#,
+        v.line, json_string(&v.password), json_string(&v.rule.to_string()), observed, json_string(&v.fix())
+    )
+}
+
+/// Lists the rules broken for `part` of `input`, one per line or as a JSON array.
+pub fn report(part: Part, input: &str, format: Format) -> Result<String, ParseError> {
+    let pairs = solver::parse::<Day2>(input)?;
+    let found = violations(&pairs, part);
+
+    let lines: Vec<String> = match format {
+        Format::Json => found.iter().map(json_violation).collect(),
+        _ => found.iter().map(Violation::to_string).collect()
+    };
+
+    Ok(match format {
+        Format::Json if lines.is_empty() => "[]\n".to_string(),
+        Format::Json => format!("[\n  {}\n]\n", lines.join(",\n  ")),
+        _ => lines.iter().map(|l| format!("{}\n", l)).collect()
+    })
+}
+
+/// Part one checks each policy as written, part two reads every rule's
+/// numbers as positions exactly one of which matches.
+fn valid_for(part: Part) -> fn(&Policy, &str) -> bool {
+    match part {
+        Part::One => Policy::accepts,
+        Part::Two => |policy, password| policy.rules.iter().all(|rule| rule.is_valid_password_part_two(password))
+    }
+}
+
+/// Solves `part` reading the input from `reader` in bounded memory.
+pub fn stream(part: Part, reader: &mut dyn BufRead) -> Result<Option<Answer>, StreamError> {
+    Ok(Some(valid_passwords_stream(reader, valid_for(part))?.into()))
+}
+
+pub struct Day2;
+
+impl Solver for Day2 {
+    const DAY: usize = 2;
+
+    type Input = Vec<(Policy, String)>;
+    type One = usize;
+    type Two = usize;
+
+    fn parse(input: &str) -> Result<Self::Input, ParseError> {
+        pairs(input)
+    }
+
+    fn part_one(pairs: &Self::Input) -> usize {
+        valid_passwords(pairs, valid_for(Part::One))
+    }
+
+    fn part_two(pairs: &Self::Input) -> usize {
+        valid_passwords(pairs, valid_for(Part::Two))
+    }
+}
+
+/// Example from the puzzle text.
+pub const EXAMPLE: &str = "1-3 a: abcde\n1-3 b: cdefg\n2-9 c: ccccccccc";
+
+#[test]
+fn example() {
+    let pairs = Day2::parse(EXAMPLE).expect("correct parse");
+    assert_eq!(Day2::part_one(&pairs), 2);
+    assert_eq!(Day2::part_two(&pairs), 1);
+    assert_eq!(valid_passwords_stream(EXAMPLE.as_bytes(), valid_for(Part::Two)).unwrap(), 1);
+}
+
+#[test]
+fn policies() {
+    let policy: Policy = "2-4 [a-c], 1&3 x, 2|9 [^0-9], 0-1 ab".parse().expect("valid policy");
+    assert_eq!(policy.to_string(), "2-4 [a-c], 1&3 x, 2|9 [^0-9], 0-1 ab");
+    assert_eq!(policy.rules[1].check, Check::And);
+
+    assert!(policy.accepts("xaxcb"));
+    assert!(!policy.accepts("xabcb"));
+    assert!(!policy.accepts("xaycb"));
+    assert!(!policy.accepts("x1xcc"));
+
+    let word: Rule = "2^4 ana".parse().unwrap();
+    assert!(!word.accepts("banana"));
+    assert!(word.is_valid_password_part_one("banana"));
+    assert!(word.holds(Check::Or, "banana"));
+
+    let beyond: Rule = "1-5 a".parse().unwrap();
+    assert!(!beyond.is_valid_password_part_two("abc"));
+    assert_eq!(Day2::parse("1-5 a: abc").map(|pairs| Day2::part_two(&pairs)), Ok(0));
+    assert_eq!(violations(&Day2::parse("1-5 a: abc").unwrap(), Part::Two)[0].fix(), "needs at least 5 characters");
+
+    assert_eq!("0^3 a".parse::<Rule>().unwrap_err().text, "0");
+    assert_eq!("1-3 a!".parse::<Rule>().unwrap_err().text, "!");
+    assert_eq!("1-3 a, 2-x b".parse::<Policy>().unwrap_err().text, "2-x b");
+}
+
+#[test]
+fn violation_report() {
+    let pairs = Day2::parse(EXAMPLE).unwrap();
+
+    let one = violations(&pairs, Part::One);
+    assert_eq!(one.len(), 1);
+    assert_eq!(one[0].to_string(), "line 2: cdefg breaks 1-3 b: found 0 occurrences, needs 1 more `b`");
+
+    let two: Vec<_> = violations(&pairs, Part::Two).iter().map(Violation::to_string).collect();
+    assert_eq!(two, [
+        "line 2: cdefg breaks 1^3 b: found `c` and `e`, `b` must be at 1 or 3",
+        "line 3: ccccccccc breaks 2^9 c: found `c` and `c`, `c` must not be at both 2 and 9"
+    ]);
+
+    let both = Day2::parse("2&9 [a-c], 1-1 z: abc").unwrap();
+    let json = report(Part::One, "2&9 [a-c], 1-1 z: abc", Format::Json).unwrap();
+    assert_eq!(violations(&both, Part::One)[0].fix(), "`[a-c]` must also be at 9");
+    assert_eq!(json, concat!(
+        "[\n",
+        r#"  {"line": 1, "password": "abc", "rule": "2&9 [a-c]", "observed": {"positions": [{"position": 2, "found": "b", "matches": true}, {"position": 9, "found": null, "matches": false}]}, "fix": "`[a-c]` must also be at 9"},"#, "\n",
+        r#"  {"line": 1, "password": "abc", "rule": "1-1 z", "observed": {"count": 0}, "fix": "needs 1 more `z`"}"#, "\n",
+        "]\n"
+    ));
+}
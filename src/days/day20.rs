@@ -0,0 +1,414 @@
+//! Day 20: Jurassic Jigsaw.
+
+use std::mem;
+use smallvec::{smallvec, SmallVec};
+use itertools::Itertools;
+
+use crate::error::{ParseError, parse_as};
+use crate::grid::{Grid, ToChar, inverse_transform, pixel};
+use crate::solver::Solver;
+
+pub type Tile<T> = Grid<T>;
+
+/// Square camera tiles keyed by id, all of side `tile_side`.
+#[derive(Debug)]
+pub struct Stmt {
+    pub tile_side: usize,
+    pub tiles: Vec<(usize, Tile<bool>)>
+}
+
+fn stmt(input: &str) -> Result<Stmt, ParseError> {
+    let lines: Vec<&str> = input.lines().map(str::trim).collect();
+
+    let mut tiles = Vec::new();
+    let mut tile_side = None;
+
+    for block in lines.split(|line| line.is_empty()).filter(|block| !block.is_empty()) {
+        let (&header, rows) = block.split_first().expect("non empty");
+
+        let id = header.strip_prefix("Tile ").and_then(|h| h.strip_suffix(":"))
+            .ok_or_else(|| ParseError::new(header, "`Tile <id>:`"))?;
+        let id = parse_as(id, "a tile id")?;
+
+        let tile = Grid::from_lines(rows.iter().copied(), "`.` or `#`", pixel)?;
+
+        // Borders are read as bits of a `u32`, and a tile keeps its inside.
+        let side = *tile_side.get_or_insert(tile.height());
+        if !(3..=32).contains(&side) {
+            return Err(ParseError::new(header, "a tile of 3 to 32 pixels a side"));
+        }
+
+        if tile.width() != side || tile.height() != side {
+            return Err(ParseError::new(header, format!("a tile of {0} by {0} pixels", side)));
+        }
+
+        tiles.push((id, tile));
+    }
+
+    Ok(Stmt { tile_side: tile_side.unwrap_or(0), tiles })
+}
+
+#[derive(Clone, Copy, Debug)]
+struct Borders {
+    left: u32,
+    right: u32,
+    top: u32,
+    bottom: u32
+}
+
+impl Borders {
+    fn to_smallvec(self) -> SmallVec<[u32; 4]> {
+        smallvec![self.left, self.right, self.top, self.bottom]
+    }
+}
+
+type Variants = [Borders; 8];
+
+fn b_to_usize(bools: impl Iterator<Item=bool>) -> u32 {
+    bools.fold(0u32, |a, c| a + a + (c as u32))
+}
+
+fn b_reverse(tile_side: usize, code: u32) -> u32 {
+    u32::reverse_bits(code) >> (32 - tile_side as u32)
+}
+
+fn borders(tile_side: usize, t: &Tile<bool>) -> Borders {
+    let top = b_to_usize((0..tile_side).map(|i| t[(i, 0)]));
+    let bottom = b_to_usize((0..tile_side).map(|i| t[(i, tile_side-1)]));
+    let left = b_to_usize((0..tile_side).map(|i| t[(0, i)]));
+    let right = b_to_usize((0..tile_side).map(|i| t[(tile_side-1, i)]));
+
+    Borders { left, right, top, bottom }
+}
+
+fn variants(tile_side: usize, t: &Tile<bool>) -> Variants {
+    let mut ret = [borders(tile_side, t); 8];
+    for (ti, t) in ret.iter_mut().enumerate().skip(1) {
+
+        if ti & 1 != 0 {
+            mem::swap(&mut t.left, &mut t.right);
+            t.top = b_reverse(tile_side, t.top);
+            t.bottom = b_reverse(tile_side, t.bottom);
+        }
+
+        if ti & 2 != 0 {
+            mem::swap(&mut t.top, &mut t.bottom);
+            t.left = b_reverse(tile_side, t.left);
+            t.right = b_reverse(tile_side, t.right);
+        }
+
+        if ti & 4 != 0 {
+            mem::swap(&mut t.top, &mut t.left);
+            mem::swap(&mut t.bottom, &mut t.right);
+        }
+    }
+
+    ret
+}
+
+#[derive(Debug, Eq, PartialEq, Clone, Copy)]
+enum Waters {
+    Spaces,
+    Hash,
+    Monster
+}
+
+impl ToChar for Waters {
+    fn to_char(&self) -> char {
+        match self {
+            Waters::Spaces => '.',
+            Waters::Hash => '#',
+            Waters::Monster => 'O'
+        }
+    }
+}
+
+/// Puts the placed tiles together, row by row, without their borders.
+fn stitch_tiles(image_side: usize, tile_side: usize, tiles: &[Tile<bool>]) -> Tile<Waters> {
+    let inner = tile_side - 2;
+    let views: Vec<_> = tiles.iter()
+        .map(|tile| tile.view((1, 1), inner, inner).expect("tiles have a border"))
+        .collect();
+
+    Grid::from_fn(image_side * inner, image_side * inner, |(x, y)| {
+        let view = views[(y / inner) * image_side + x / inner];
+        if view[(x % inner, y % inner)] { Waters::Hash } else { Waters::Spaces }
+    })
+}
+
+type Assignment = Vec<(usize, usize)>;
+
+fn pruning_search(variants: &[(usize, Variants)]) -> Option<(usize, Assignment)> {
+    let image_side = (0..).take_while(|s| s*s <= variants.len()).find(|s| s*s == variants.len())?;
+
+    fn rec(
+        variants: &[(usize, Variants)],
+        assignment: &mut Assignment,
+        used: &mut [bool],
+        bottoms: &mut Vec<u32>,
+        right: u32,
+        image_side: usize) -> Result<(), Assignment>
+    {
+        let k = assignment.len();
+
+        if k == image_side * image_side {
+            return Err(assignment.clone());
+        }
+
+        let (i, j) = (k / image_side, k % image_side);
+
+        for (vi, &(id, variant)) in variants.iter().enumerate() {
+            if !used[vi] {
+                used[vi] = true;
+
+                for (ti, borders) in variant.iter().enumerate() {
+                    if (i == 0 || borders.top == bottoms[k - image_side])
+                        && (j == 0 || borders.left == right) {
+                            assignment.push((id, ti));
+                            bottoms.push(borders.bottom);
+
+                            rec(variants, assignment, used, bottoms, borders.right, image_side)?;
+
+                            bottoms.pop();
+                            assignment.pop();
+                        }
+                }
+
+                used[vi] = false;
+            }
+        }
+
+        Ok(())
+    }
+
+    let mut assignment = vec![];
+    let mut used = vec![false; variants.len()];
+    let mut bottoms = vec![];
+
+    let ids = rec(variants, &mut assignment, &mut used, &mut bottoms, 0, image_side).err()?;
+
+    Some((image_side, ids))
+}
+
+const SEA_MONSTER: &str = "                  # \n#    ##    ##    ###\n #  #  #  #  #  #   ";
+
+fn mark_seamonsters(image: &mut Tile<Waters>) {
+    let monster = Grid::from_lines(SEA_MONSTER.lines(), "` ` or `#`", |c| Some(c == '#')).expect("valid monster");
+    let (mw, mh) = (monster.width(), monster.height());
+
+    for ti in 0..8 {
+        let mut flipped = image.transformed(ti);
+
+        let found: Vec<_> = flipped.positions()
+            .filter(|&pos| {
+                flipped.view(pos, mw, mh).is_some_and(|view| {
+                    monster.positions().all(|p| !monster[p] || view[p] != Waters::Spaces)
+                })
+            })
+            .collect();
+
+        for (x, y) in found {
+            for (dx, dy) in monster.positions().filter(|&p| monster[p]) {
+                flipped[(x + dx, y + dy)] = Waters::Monster;
+            }
+        }
+
+        *image = flipped.transformed(inverse_transform(ti));
+    }
+}
+
+pub struct Day20;
+
+impl Solver for Day20 {
+    const DAY: usize = 20;
+
+    type Input = Stmt;
+    type One = usize;
+    type Two = usize;
+
+    fn parse(input: &str) -> Result<Stmt, ParseError> {
+        stmt(input)
+    }
+
+    fn part_one(stmt: &Stmt) -> usize {
+        let borders: Vec<Borders> = stmt.tiles.iter()
+            .map(|(_, t)| borders(stmt.tile_side, t))
+            .collect();
+
+        let edges: Vec<u32> = borders.iter()
+            .flat_map(|b| b.to_smallvec())
+            .map(|bin| bin.min(b_reverse(stmt.tile_side, bin)))
+            .collect();
+
+        let unique_edges: Vec<u32> = edges.into_iter()
+            .sorted()
+            .group_by(|&k| k)
+            .into_iter()
+            .filter_map(|(edge, group)| {
+                if group.count() > 1 { None } else { Some(edge) }
+            })
+            .collect();
+
+        let tile_ids = unique_edges.into_iter()
+            .map(|edge| {
+                let pos = borders.iter().position(|b| {
+                    let sv = b.to_smallvec();
+                    sv.contains(&edge) || sv.contains(&b_reverse(stmt.tile_side, edge))
+                })?;
+
+                let &(id, _) = stmt.tiles.get(pos)?;
+                Some(id)
+            })
+            .collect::<Option<Vec<usize>>>().expect("all belong to borders");
+
+        tile_ids.into_iter()
+            .sorted()
+            .group_by(|&k| k)
+            .into_iter()
+            .filter_map(|(edge, group)| {
+                if group.count() == 2 { Some(edge) } else { None }
+            })
+            .product()
+    }
+
+    fn part_two(stmt: &Stmt) -> usize {
+        let variants: Vec<_> = stmt.tiles.iter().map(|(id, t)| (*id, variants(stmt.tile_side, t))).collect();
+        let (image_side, ids) = pruning_search(&variants[..]).expect("solution exists");
+
+        let tiles = ids.into_iter().map(|(id, ti)| {
+            stmt.tiles.iter().find_map(|(tile_id, tile)| {
+                if id != *tile_id {
+                    return None;
+                }
+
+                Some(tile.transformed(ti))
+            })
+        }).collect::<Option<Vec<_>>>().expect("search works, qed.");
+
+        let mut image = stitch_tiles(image_side, stmt.tile_side, &tiles);
+
+        mark_seamonsters(&mut image);
+
+        image.cells().filter(|&&p| p == Waters::Hash).count()
+    }
+}
+
+/// Example from the puzzle text.
+pub const EXAMPLE: &str = r"Tile 2311:
+..##.#..#.
+##..#.....
+#...##..#.
+####.#...#
+##.##.###.
+##...#.###
+.#.#.#..##
+..#....#..
+###...#.#.
+..###..###
+
+Tile 1951:
+#.##...##.
+#.####...#
+.....#..##
+#...######
+.##.#....#
+.###.#####
+###.##.##.
+.###....#.
+..#.#..#.#
+#...##.#..
+
+Tile 1171:
+####...##.
+#..##.#..#
+##.#..#.#.
+.###.####.
+..###.####
+.##....##.
+.#...####.
+#.##.####.
+####..#...
+.....##...
+
+Tile 1427:
+###.##.#..
+.#..#.##..
+.#.##.#..#
+#.#.#.##.#
+....#...##
+...##..##.
+...#.#####
+.#.####.#.
+..#..###.#
+..##.#..#.
+
+Tile 1489:
+##.#.#....
+..##...#..
+.##..##...
+..#...#...
+#####...#.
+#..#.#.#.#
+...#.#.#..
+##.#...##.
+..##.##.##
+###.##.#..
+
+Tile 2473:
+#....####.
+#..#.##...
+#.##..#...
+######.#.#
+.#...#.#.#
+.#########
+.###.#..#.
+########.#
+##...##.#.
+..###.#.#.
+
+Tile 2971:
+..#.#....#
+#...###...
+#.#.###...
+##.##..#..
+.#####..##
+.#..####.#
+#..#.#..#.
+..####.###
+..#.#.###.
+...#.#.#.#
+
+Tile 2729:
+...#.#.#.#
+####.#....
+..#.#.....
+....#..#.#
+.##..##.#.
+.#.####...
+####.#.#..
+##.####...
+##..#.##..
+#.##...##.
+
+Tile 3079:
+#.#.#####.
+.#..######
+..#.......
+######....
+####.#..#.
+.#...#.##.
+#.#####.##
+..#.###...
+..#.......
+..#.###...";
+
+#[test]
+fn example() {
+    let stmt = Day20::parse(EXAMPLE).expect("correct parse");
+    assert_eq!(Day20::part_one(&stmt), 20899048083289);
+    assert_eq!(Day20::part_two(&stmt), 273);
+
+    assert_eq!(Day20::parse("Tile 1:\n#").unwrap_err().expected, "a tile of 3 to 32 pixels a side");
+    let wide = format!("Tile 1:\n{}", vec!["#".repeat(33); 33].join("\n"));
+    assert!(Day20::parse(&wide).is_err());
+}
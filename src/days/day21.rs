@@ -0,0 +1,128 @@
+//! Day 21: Allergen Assessment.
+
+use std::collections::{HashSet, HashMap, hash_map::Entry};
+
+use regex::Regex;
+
+use crate::error::ParseError;
+use crate::solver::Solver;
+
+lazy_static! {
+    static ref LINE_RE: Regex = Regex::new(r"([^(]+)\(contains ([^)]+)\)").expect("compiles");
+}
+
+/// One line of the food list: ingredients and the allergens known to be among them.
+#[derive(Debug)]
+pub struct Food {
+    pub ingredients: Vec<String>,
+    pub allergens: Vec<String>
+}
+
+fn foods(input: &str) -> Result<Vec<Food>, ParseError> {
+    input.lines().map(str::trim).map(|line| {
+        let captures = LINE_RE.captures(line)
+            .ok_or_else(|| ParseError::new(line, "`<ingredients> (contains <allergens>)`"))?;
+
+        let ingredients = &captures[1];
+        let allergens = &captures[2];
+
+        let ingredients = ingredients.split_whitespace().map(str::to_string).collect();
+        let allergens = allergens.split(", ").map(str::to_string).collect();
+
+        Ok(Food { ingredients, allergens })
+    }).collect()
+}
+
+/// Occurrences of allergen-free ingredients and the canonical dangerous ingredient list.
+pub fn part_both(foods: &[Food]) -> (usize, String) {
+    let all_ingredients: HashSet<String> = foods.iter().flat_map(|f| f.ingredients.iter()).cloned().collect();
+
+    let mut allergen_source: HashMap<String, HashSet<String>> = HashMap::new();
+
+    for food in foods.iter() {
+        let cur_ingredients: HashSet<String> = food.ingredients.iter().cloned().collect();
+        for allergen in food.allergens.iter().cloned() {
+            match allergen_source.entry(allergen) {
+                Entry::Occupied(mut oe) => {
+                    let vr = oe.get_mut();
+                    *vr = vr.intersection(&cur_ingredients).cloned().collect();
+                },
+
+                Entry::Vacant(ve) => {
+                    ve.insert(cur_ingredients.clone());
+                }
+            }
+        }
+    }
+
+    let mut identified_allergens = HashSet::new();
+    let mut identified_ingredients = HashSet::new();
+    let mut identifieds = Vec::<(String, String)>::new();
+
+    while !allergen_source.is_empty() {
+        for (allergen, ingredients) in allergen_source.clone() {
+            if ingredients.len() == 1 {
+                let ingredient: String = ingredients.iter().next().cloned().expect("exactly one");
+                identified_allergens.insert(allergen.clone());
+                identified_ingredients.insert(ingredient.clone());
+
+                identifieds.push((allergen, ingredient));
+
+            } else {
+                let less_ingredients = allergen_source
+                    .remove(&allergen).expect("iterator invariants")
+                    .difference(&identified_ingredients)
+                    .cloned()
+                    .collect();
+
+                allergen_source.insert(allergen, less_ingredients);
+            }
+        }
+
+        for allergen in &identified_allergens {
+            allergen_source.remove(allergen);
+        }
+    }
+
+    let impossibru_ingredients: HashSet<String> = all_ingredients.difference(&identified_ingredients).cloned().collect();
+
+    identifieds.sort();
+    let canonical_list = identifieds.into_iter().map(|(_, v)| v).collect::<Vec<_>>().join(",");
+    let impossibru_occ = foods.iter().flat_map(|f| f.ingredients.iter()).filter(|&i| impossibru_ingredients.contains(i)).count();
+
+    (impossibru_occ, canonical_list)
+}
+
+pub struct Day21;
+
+impl Solver for Day21 {
+    const DAY: usize = 21;
+
+    type Input = Vec<Food>;
+    type One = usize;
+    type Two = String;
+
+    fn parse(input: &str) -> Result<Vec<Food>, ParseError> {
+        foods(input)
+    }
+
+    fn part_one(foods: &Vec<Food>) -> usize {
+        part_both(foods).0
+    }
+
+    fn part_two(foods: &Vec<Food>) -> String {
+        part_both(foods).1
+    }
+}
+
+/// Example from the puzzle text.
+pub const EXAMPLE: &str = r"mxmxvkd kfcds sqjhc nhms (contains dairy, fish)
+trh fvjkl sbzzf mxmxvkd (contains dairy)
+sqjhc fvjkl (contains soy)
+sqjhc mxmxvkd sbzzf (contains fish)";
+
+#[test]
+fn example() {
+    let foods = Day21::parse(EXAMPLE).expect("correct parse");
+    assert_eq!(part_both(&foods), (5, "mxmxvkd,sqjhc,fvjkl".to_string()));
+}
@@ -0,0 +1,155 @@
+//! Day 22: Crab Combat.
+
+use std::collections::{VecDeque, HashSet};
+
+use crate::error::{ParseError, parse_as};
+use crate::solver::Solver;
+
+pub type Deck = VecDeque<usize>;
+
+fn decks(input: &str) -> Result<(Deck, Deck), ParseError> {
+    let lines: Vec<_> = input.lines().map(str::trim).collect();
+    let parts: Vec<&[&str]> = lines.split(|s| s.is_empty()).filter(|part| !part.is_empty()).collect();
+
+    if let Some(extra) = parts.get(2) {
+        return Err(ParseError::new(extra[0], "the end of the input"));
+    }
+
+    let deck = |i: usize| -> Result<Deck, ParseError> {
+        let expected = format!("`Player {}:`", i+1);
+        let (&header, cards) = parts.get(i).and_then(|part| part.split_first())
+            .ok_or_else(|| ParseError::end(input, expected.as_str()))?;
+
+        if format!("`{}`", header) != expected {
+            return Err(ParseError::new(header, expected));
+        }
+
+        cards.iter().map(|c| parse_as(c, "a card")).collect()
+    };
+
+    Ok((deck(0)?, deck(1)?))
+}
+
+#[derive(Copy, Clone)]
+enum Winner {
+    First,
+    Second
+}
+
+fn score(deck_1: Deck, deck_2: Deck, winner: Winner) -> usize {
+    let deck = match winner {
+        Winner::First => deck_1,
+        Winner::Second => deck_2
+    };
+
+    deck.into_iter().rev().enumerate().map(|(i, v)| (i+1) * v).sum()
+}
+
+fn play_combat(deck_1: &mut Deck, deck_2: &mut Deck) -> Winner {
+    while !deck_1.is_empty() && !deck_2.is_empty() {
+        let front_1 = deck_1.pop_front().expect("both nonempty");
+        let front_2 = deck_2.pop_front().expect("both nonempty");
+
+        if front_1 > front_2 {
+            deck_1.push_back(front_1);
+            deck_1.push_back(front_2);
+
+        } else {
+            deck_2.push_back(front_2);
+            deck_2.push_back(front_1);
+
+        }
+    }
+
+    if deck_1.is_empty() { Winner::Second } else { Winner::First }
+}
+
+type Visited = HashSet<(Deck, Deck)>;
+
+fn play_recursive_combat(deck_1: &mut Deck, deck_2: &mut Deck) -> Winner {
+    let mut visited = Visited::new();
+
+    loop {
+        if !visited.insert((deck_1.clone(), deck_2.clone())) {
+            return Winner::First;
+        }
+
+        if deck_1.is_empty() || deck_2.is_empty() {
+            break;
+        }
+
+        let front_1 = deck_1.pop_front().expect("both nonempty");
+        let front_2 = deck_2.pop_front().expect("both nonempty");
+
+        let winner = if deck_1.len() >= front_1 && deck_2.len() >= front_2 {
+            let mut deck_1 = deck_1.iter().take(front_1).cloned().collect();
+            let mut deck_2 = deck_2.iter().take(front_2).cloned().collect();
+            play_recursive_combat(&mut deck_1, &mut deck_2)
+        } else {
+            if front_1 > front_2 { Winner::First } else { Winner::Second }
+        };
+
+        match winner {
+            Winner::First => {
+                deck_1.push_back(front_1);
+                deck_1.push_back(front_2);
+            },
+
+            Winner::Second => {
+                deck_2.push_back(front_2);
+                deck_2.push_back(front_1);
+            }
+        }
+    }
+
+    if deck_1.is_empty() { Winner::Second } else { Winner::First }
+}
+
+pub struct Day22;
+
+impl Solver for Day22 {
+    const DAY: usize = 22;
+
+    type Input = (Deck, Deck);
+    type One = usize;
+    type Two = usize;
+
+    fn parse(input: &str) -> Result<(Deck, Deck), ParseError> {
+        decks(input)
+    }
+
+    fn part_one(decks: &(Deck, Deck)) -> usize {
+        let (mut deck_1, mut deck_2) = decks.clone();
+        let winner = play_combat(&mut deck_1, &mut deck_2);
+        score(deck_1, deck_2, winner)
+    }
+
+    fn part_two(decks: &(Deck, Deck)) -> usize {
+        let (mut deck_1, mut deck_2) = decks.clone();
+        let winner = play_recursive_combat(&mut deck_1, &mut deck_2);
+        score(deck_1, deck_2, winner)
+    }
+}
+
+/// Example from the puzzle text.
+pub const EXAMPLE: &str = r"Player 1:
+9
+2
+6
+3
+1
+
+Player 2:
+5
+8
+4
+7
+10
+";
+
+#[test]
+fn example() {
+    let decks = Day22::parse(EXAMPLE).expect("correct parse");
+    assert_eq!(Day22::part_one(&decks), 306);
+    assert_eq!(Day22::part_two(&decks), 291);
+}
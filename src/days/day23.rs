@@ -0,0 +1,183 @@
+//! Day 23: Crab Cups.
+
+use std::char;
+use std::collections::{VecDeque, HashMap};
+
+use crate::error::{ParseError, only_chars};
+use crate::solver::Solver;
+
+const P1_LABELS: u32 = 10;
+const P1_MOVES: usize = 100;
+
+const P2_LABELS: u32 = 1_000_000;
+const P2_MOVES: usize = 10_000_000;
+
+pub type Circle = VecDeque<u32>;
+
+fn circle(input: &str) -> Result<Circle, ParseError> {
+    let labels = input.trim();
+    only_chars(labels, "123456789", "a cup label from 1 to 9")?;
+
+    for (i, c) in labels.char_indices() {
+        if labels[..i].contains(c) {
+            return Err(ParseError::new(&labels[i..=i], "each cup label once"));
+        }
+    }
+
+    if labels.len() != 9 {
+        return Err(ParseError::end(labels, "every cup label from 1 to 9"));
+    }
+
+    Ok(labels.chars().filter_map(|c| c.to_digit(10)).collect())
+}
+
+fn do_move_p1(circle: &mut Circle) {
+    let current = circle.front().cloned().expect("should be present");
+    circle.rotate_left(1);
+
+    let cup_1 = circle.pop_front().expect("should be present");
+    let cup_2 = circle.pop_front().expect("should be present");
+    let cup_3 = circle.pop_front().expect("should be present");
+
+    let dest_pos = (1..=10).map(|d| (current + P1_LABELS - d) % P1_LABELS)
+        .find_map(|dl| circle.iter().rposition(|&cl| dl == cl))
+        .expect("destination should exist") + 1;
+
+    circle.insert(dest_pos, cup_3);
+    circle.insert(dest_pos, cup_2);
+    circle.insert(dest_pos, cup_1);
+}
+
+#[derive(Debug)]
+struct CircleList {
+    current: u32,
+    prev: HashMap<u32, u32>,
+    next: HashMap<u32, u32>
+}
+
+impl CircleList {
+    fn from_circle(mut circle: Circle) -> Self {
+        let slice = circle.make_contiguous();
+
+        let mut prev = HashMap::new();
+        let mut next = HashMap::new();
+
+        for w in slice.windows(2) {
+            next.insert(w[0], w[1]);
+            prev.insert(w[1], w[0]);
+        }
+
+        let first = slice.first().cloned().expect("at least one");
+        let last = slice.last().cloned().expect("at least one");
+
+        next.insert(last, first);
+        prev.insert(first, last);
+
+        Self { current: first, prev, next }
+    }
+
+    fn pop_after_current(&mut self) -> Option<u32> {
+        let ret_key = self.next.get(&self.current).cloned()?;
+
+        let ret_next = self.next.remove(&ret_key)?;
+        let ret_prev = self.prev.remove(&ret_key)?;
+        assert!(ret_prev == self.current);
+
+        self.next.insert(ret_prev, ret_next);
+        self.prev.insert(ret_next, ret_prev);
+
+        Some(ret_key)
+    }
+
+    fn push_after_key(&mut self, key: u32, after_key: u32) {
+        let key_next = self.next.remove(&key).expect("key should be present");
+
+        self.next.insert(key, after_key);
+        self.next.insert(after_key, key_next);
+
+        self.prev.insert(key_next, after_key);
+        self.prev.insert(after_key, key);
+    }
+
+    fn advance_current(&mut self) {
+        self.current = self.next.get(&self.current).cloned().expect("current consistent");
+    }
+}
+
+fn do_move_p2(circle_list: &mut CircleList) {
+    let cup_1 = circle_list.pop_after_current().expect("should pop");
+    let cup_2 = circle_list.pop_after_current().expect("should pop");
+    let cup_3 = circle_list.pop_after_current().expect("should pop");
+
+    let dest_key = (2..=10).map(|d| (circle_list.current + P2_LABELS - d) % P2_LABELS + 1)
+        .find(|&dk| dk != cup_1 && dk != cup_2 && dk != cup_3)
+        .expect("destination exists");
+
+    circle_list.push_after_key(dest_key, cup_3);
+    circle_list.push_after_key(dest_key, cup_2);
+    circle_list.push_after_key(dest_key, cup_1);
+
+    circle_list.advance_current();
+}
+
+pub struct Day23;
+
+impl Solver for Day23 {
+    const DAY: usize = 23;
+
+    type Input = Circle;
+    type One = String;
+    type Two = usize;
+
+    fn parse(input: &str) -> Result<Circle, ParseError> {
+        circle(input)
+    }
+
+    fn part_one(circle: &Circle) -> String {
+        let mut circle = circle.clone();
+
+        for _ in 0..P1_MOVES {
+            do_move_p1(&mut circle);
+        }
+
+        let shift = circle.iter().position(|&c| c == 1).expect("1 present");
+        circle.rotate_left(shift);
+        circle.pop_front().expect("non empty");
+
+        circle.into_iter().map(|c| char::from_digit(c, 10).expect("valid decimal")).collect()
+    }
+
+    fn part_two(circle: &Circle) -> usize {
+        let mut circle = circle.clone();
+
+        circle.extend(P1_LABELS..=P2_LABELS);
+
+        let mut circle_list = CircleList::from_circle(circle);
+
+        for _ in 0..P2_MOVES {
+            do_move_p2(&mut circle_list);
+        }
+
+        while circle_list.current != 1 {
+            circle_list.advance_current();
+        }
+
+        let cup_1 = circle_list.pop_after_current().expect("should be present") as usize;
+        let cup_2 = circle_list.pop_after_current().expect("should be present") as usize;
+
+        cup_1 * cup_2
+    }
+}
+
+/// Example from the puzzle text.
+pub const EXAMPLE: &str = "389125467";
+
+#[test]
+fn example() {
+    let circle = Day23::parse(EXAMPLE).expect("correct parse");
+    assert_eq!(Day23::part_one(&circle), "67384529");
+    assert_eq!(Day23::part_two(&circle), 149245887792);
+
+    assert_eq!(Day23::parse("1").unwrap_err().expected, "every cup label from 1 to 9");
+    assert_eq!(Day23::parse("11").unwrap_err().expected, "each cup label once");
+}
@@ -0,0 +1,120 @@
+//! Day 24: Lobby Layout.
+
+use std::collections::BTreeSet;
+
+use crate::automaton::{Automaton, Hex};
+use crate::error::ParseError;
+use crate::solver::Solver;
+
+const P2_DAYS: usize = 100;
+
+/// Axial hex coordinates, also used for single steps.
+pub type Dir = (isize, isize);
+
+const DIRS: [(&str, Dir); 6] = [
+    ("e", (1, 0)),
+    ("w", (-1, 0)),
+    ("sw", (0, -1)),
+    ("se", (1, -1)),
+    ("nw", (-1, 1)),
+    ("ne", (0, 1))
+];
+
+fn consume_dir(s: &str) -> Option<(Dir, &str)> {
+    DIRS.iter().find(|(pat, _)| s.starts_with(pat)).map(|(pat, dir)| (*dir, &s[pat.len()..]))
+}
+
+fn tiles(input: &str) -> Result<Vec<Vec<Dir>>, ParseError> {
+    input.lines().map(str::trim).map(|mut l| {
+        let mut dirs = Vec::new();
+
+        while !l.is_empty() {
+            let (dir, l_) = consume_dir(l)
+                .ok_or_else(|| ParseError::new(l, "a direction, one of `e`, `se`, `sw`, `w`, `nw` and `ne`"))?;
+
+            l = l_;
+            dirs.push(dir);
+        }
+
+        Ok(dirs)
+    }).collect()
+}
+
+fn sum_dirs(dirs: &[Dir]) -> Dir {
+    dirs.iter().fold((0, 0), |(ax, ay), (x, y)| (ax+x, ay+y))
+}
+
+/// The black tiles of the hex floor.
+pub type Tileset = BTreeSet<Dir>;
+
+/// The lobby's flipping rules: a white tile next to two black ones turns
+/// black, a black one next to none or more than two turns white.
+pub fn lobby() -> Automaton<Hex> {
+    Automaton::new(Hex, "B2/S12".parse().expect("valid rule"))
+}
+
+/// Flips the tile at the end of every path, starting from all white.
+pub fn starting_tileset(tiles: &[Vec<Dir>]) -> Tileset {
+    let mut tileset = Tileset::new();
+
+    for tile in tiles {
+        let sum = sum_dirs(tile);
+
+        if !tileset.remove(&sum) {
+            tileset.insert(sum);
+        }
+    }
+
+    tileset
+}
+
+pub struct Day24;
+
+impl Solver for Day24 {
+    const DAY: usize = 24;
+
+    type Input = Vec<Vec<Dir>>;
+    type One = usize;
+    type Two = usize;
+
+    fn parse(input: &str) -> Result<Vec<Vec<Dir>>, ParseError> {
+        tiles(input)
+    }
+
+    fn part_one(tiles: &Vec<Vec<Dir>>) -> usize {
+        starting_tileset(tiles).len()
+    }
+
+    fn part_two(tiles: &Vec<Vec<Dir>>) -> usize {
+        lobby().run(&starting_tileset(tiles), P2_DAYS).len()
+    }
+}
+
+/// Example from the puzzle text.
+pub const EXAMPLE: &str = r"sesenwnenenewseeswwswswwnenewsewsw
+neeenesenwnwwswnenewnwwsewnenwseswesw
+seswneswswsenwwnwse
+nwnwneseeswswnenewneswwnewseswneseene
+swweswneswnenwsewnwneneseenw
+eesenwseswswnenwswnwnwsewwnwsene
+sewnenenenesenwsewnenwwwse
+wenwwweseeeweswwwnwwe
+wsweesenenewnwwnwsenewsenwwsesesenwne
+neeswseenwwswnwswswnw
+nenwswwsewswnenenewsenwsenwnesesenew
+enewnwewneswsewnwswenweswnenwsenwsw
+sweneswneswneneenwnewenewwneswswnese
+swwesenesewenwneswnwwneseswwne
+enesenwswwswneneswsenwnewswseenwsese
+wnwnesenesenenwwnenwsewesewsesesew
+nenewswnwewswnenesenwnesewesw
+eneswnwswnwsenenwnwnwwseeswneewsenese
+neswnwewnwnwseenwseesewsenwsweewe
+wseweeenwnesenwwwswnew";
+
+#[test]
+fn example() {
+    let tiles = Day24::parse(EXAMPLE).expect("correct parse");
+    assert_eq!(Day24::part_one(&tiles), 10);
+    assert_eq!(Day24::part_two(&tiles), 2208);
+}
@@ -0,0 +1,77 @@
+//! Day 25: Combo Breaker.
+
+use crate::error::{ParseError, parse_as};
+use crate::solver::Solver;
+
+const GEN: usize = 7;
+const MODULO: usize = 20201227;
+
+fn dlog(exp: usize) -> usize {
+    let mut cur = 1usize;
+
+    for dlog in 0.. {
+        if cur == exp {
+            return dlog;
+        }
+
+        cur *= GEN;
+        cur %= MODULO;
+    }
+
+    unreachable!()
+}
+
+fn exp(dlog: usize) -> usize {
+    let mut ret = 1usize;
+    let mut pow2 = dlog.next_power_of_two();
+
+    while pow2 > 0 {
+        ret = (ret * ret) % MODULO;
+
+        if pow2 & dlog != 0 {
+            ret = (ret * GEN) % MODULO;
+        }
+
+        pow2 >>= 1;
+    }
+
+    ret
+}
+
+fn public_keys(input: &str) -> Result<(usize, usize), ParseError> {
+    let mut keys = input.lines().map(|l| parse_as(l.trim(), "a public key"));
+    let dh1 = keys.next().unwrap_or_else(|| Err(ParseError::end(input, "the card's public key")))?;
+    let dh2 = keys.next().unwrap_or_else(|| Err(ParseError::end(input, "the door's public key")))?;
+    Ok((dh1, dh2))
+}
+
+pub struct Day25;
+
+impl Solver for Day25 {
+    const DAY: usize = 25;
+
+    /// Public keys of the card and the door.
+    type Input = (usize, usize);
+    type One = usize;
+    type Two = ();
+
+    fn parse(input: &str) -> Result<(usize, usize), ParseError> {
+        public_keys(input)
+    }
+
+    fn part_one(&(dh1, dh2): &(usize, usize)) -> usize {
+        exp(dlog(dh1) * dlog(dh2))
+    }
+
+    /// The last star is free.
+    fn part_two(_: &(usize, usize)) {}
+}
+
+/// Example from the puzzle text.
+pub const EXAMPLE: &str = "5764801\n17807724";
+
+#[test]
+fn example() {
+    let keys = Day25::parse(EXAMPLE).expect("correct parse");
+    assert_eq!(Day25::part_one(&keys), 14897079);
+}
@@ -0,0 +1,176 @@
+//! Day 3: Toboggan Trajectory.
+
+use std::fmt;
+use std::iter;
+use num::integer::Integer;
+
+use crate::error::ParseError;
+use crate::grid::{Grid, Pos};
+use crate::solver::Solver;
+
+fn tree_map(input: &str) -> Result<TreeMap, ParseError> {
+    Grid::parse(input, "a map character", |c| Some(c).filter(|c| !c.is_whitespace()))
+}
+
+/// The map as drawn, `#` being a tree and `.` open ground.
+pub type TreeMap = Grid<char>;
+
+/// A step of travel, `right` columns and `down` rows at a time, as given:
+/// right 2 down 2 only lands on every other row. Negative numbers go left
+/// and up.
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+pub struct Slope {
+    pub right: isize,
+    pub down: isize
+}
+
+impl Slope {
+    /// Panics if both are zero, which goes nowhere.
+    pub fn new(right: isize, down: isize) -> Self {
+        assert!(right != 0 || down != 0, "a slope has to go somewhere");
+        Slope { right, down }
+    }
+}
+
+impl fmt::Display for Slope {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "right {}, down {}", self.right, self.down)
+    }
+}
+
+/// A trip down the map, which always repeats itself to the right.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Query {
+    pub slope: Slope,
+    pub start: Pos,
+    /// Whether the map also repeats itself downwards, in which case the trip
+    /// goes on until it is back at the start.
+    pub wrap_down: bool,
+    /// What counts as a collision.
+    pub obstacles: String
+}
+
+impl Query {
+    /// From the top left corner down to the bottom, hitting trees.
+    pub fn new(slope: Slope) -> Self {
+        Query { slope, start: (0, 0), wrap_down: false, obstacles: "#".to_string() }
+    }
+
+    /// Every position visited, the start included, with the column taken
+    /// modulo the width of the map.
+    pub fn path<'a>(&'a self, map: &'a TreeMap) -> impl Iterator<Item=Pos> + 'a {
+        let (w, h) = (map.width() as isize, map.height() as isize);
+        let start = Some(self.start)
+            .filter(|&(_, y)| w > 0 && y < map.height())
+            .map(|(x, y)| (x % map.width(), y));
+
+        iter::successors(start, move |&(x, y)| {
+            let x = (x as isize + self.slope.right).rem_euclid(w) as usize;
+            let y = y as isize + self.slope.down;
+            let y = if self.wrap_down { y.rem_euclid(h) } else { Some(y).filter(|y| (0..h).contains(y))? };
+
+            Some((x, y as usize)).filter(|&pos| Some(pos) != start)
+        })
+    }
+
+    /// Obstacles hit along the way.
+    pub fn collisions(&self, map: &TreeMap) -> usize {
+        self.path(map).filter(|&pos| self.obstacles.contains(map[pos])).count()
+    }
+}
+
+/// The downward slope with the fewest collisions for `query` otherwise,
+/// going at most `max_right` columns either way and `max_down` rows per
+/// step, each direction tried in its smallest step only. Ties go to the first slope found, by `down` and then `right`.
+pub fn optimal_slope(map: &TreeMap, query: &Query, max_right: usize, max_down: usize) -> Option<(Slope, usize)> {
+    let (max_right, max_down) = (max_right as isize, max_down as isize);
+    let mut best: Option<(Slope, usize)> = None;
+
+    for down in 1..=max_down {
+        for right in -max_right..=max_right {
+            // A multiple of a smaller step is the same direction again.
+            if right.gcd(&down) != 1 {
+                continue;
+            }
+
+            let slope = Slope::new(right, down);
+            let hits = Query { slope, ..query.clone() }.collisions(map);
+
+            if best.is_none_or(|(_, fewest)| hits < fewest) {
+                best = Some((slope, hits));
+            }
+        }
+    }
+
+    best
+}
+
+fn trees_on_slope(tree_map: &TreeMap, right: isize, down: isize) -> usize {
+    Query::new(Slope::new(right, down)).collisions(tree_map)
+}
+
+pub struct Day3;
+
+impl Solver for Day3 {
+    const DAY: usize = 3;
+
+    type Input = TreeMap;
+    type One = usize;
+    type Two = usize;
+
+    fn parse(input: &str) -> Result<TreeMap, ParseError> {
+        tree_map(input)
+    }
+
+    fn part_one(tree_map: &TreeMap) -> usize {
+        trees_on_slope(tree_map, 3, 1)
+    }
+
+    fn part_two(tree_map: &TreeMap) -> usize {
+        let slopes = [(1, 1), (3, 1), (5, 1), (7, 1), (1, 2)];
+        slopes.iter().map(|&(right, down)| trees_on_slope(tree_map, right, down)).product()
+    }
+}
+
+/// Example from the puzzle text.
+pub const EXAMPLE: &str = r"..##.......
+#...#...#..
+.#....#..#.
+..#.#...#.#
+.#...##..#.
+..#.##.....
+.#.#.#....#
+.#........#
+#.##...#...
+#...##....#
+.#..#...#.#";
+
+#[test]
+fn example() {
+    let tree_map = Day3::parse(EXAMPLE).expect("correct parse");
+    assert_eq!(Day3::part_one(&tree_map), 7);
+    assert_eq!(Day3::part_two(&tree_map), 336);
+    assert_eq!((trees_on_slope(&tree_map, 1, 1), trees_on_slope(&tree_map, 2, 2)), (2, 1));
+}
+
+#[test]
+fn queries() {
+    let map = Day3::parse("#.O\n.#.\nO.#").unwrap();
+    assert_eq!(Slope::new(-4, 2), Slope { right: -4, down: 2 });
+
+    let diagonal = Query::new(Slope::new(1, 1));
+    assert_eq!(diagonal.path(&map).collect::<Vec<_>>(), vec![(0, 0), (1, 1), (2, 2)]);
+    assert_eq!(diagonal.collisions(&map), 3);
+
+    let boulders = Query { slope: Slope::new(-1, 1), obstacles: "O".to_string(), ..diagonal.clone() };
+    assert_eq!(boulders.path(&map).collect::<Vec<_>>(), vec![(0, 0), (2, 1), (1, 2)]);
+    assert_eq!(boulders.collisions(&map), 0);
+
+    let torus = Query { slope: Slope::new(1, 2), start: (2, 0), wrap_down: true, ..diagonal };
+    assert_eq!(torus.path(&map).collect::<Vec<_>>(), vec![(2, 0), (0, 2), (1, 1)]);
+    assert_eq!(torus.collisions(&map), 1);
+
+    let example = Day3::parse(EXAMPLE).unwrap();
+    let (slope, hits) = optimal_slope(&example, &Query::new(Slope::new(3, 1)), 3, 2).expect("some slope");
+    assert_eq!((slope, hits), (Slope::new(2, 1), 1));
+}
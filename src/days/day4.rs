@@ -0,0 +1,333 @@
+//! Day 4: Passport Processing.
+
+use std::fmt;
+use std::ops::RangeInclusive;
+use std::path::{Path, PathBuf};
+use smallvec::SmallVec;
+
+use crate::Part;
+use crate::error::ParseError;
+use crate::input::{self, Source};
+use crate::report::{Format, json_string};
+use crate::schema::{Issue, Schema, SchemaError};
+use crate::solver::{self, Answer, Solver};
+
+pub const SCHEMA_FILE: &str = "passport.toml";
+
+/// The schema shipped in `inputs`, for inputs with none next to them.
+const BUNDLED_SCHEMA: &str = include_str!("../../inputs/passport.toml");
+
+lazy_static! {
+    static ref PASSPORT: Schema = Schema::parse(BUNDLED_SCHEMA).expect("valid bundled schema");
+}
+
+/// The fields of a passport as bundled with the crate.
+pub fn passport_schema() -> &'static Schema {
+    &PASSPORT
+}
+
+fn passport() -> &'static Schema {
+    &PASSPORT
+}
+
+/// Reads the fields of a passport from `passport.toml` next to the inputs of
+/// `source`: in the directory given, in the one holding the input file, or
+/// in the first of [`input::search_dirs`] that has one. `None` if there is
+/// no such file, the bundled schema applying then.
+pub fn find_schema(source: &Source) -> Result<Option<Schema>, SchemaError> {
+    let dirs = match source {
+        Source::Dir(dir) => vec![dir.clone()],
+        Source::Path(path) => vec![path.parent().map_or_else(PathBuf::new, Path::to_path_buf)],
+        Source::Default | Source::Stdin => input::search_dirs()
+    };
+
+    match dirs.iter().map(|dir| dir.join(SCHEMA_FILE)).find(|path| path.is_file()) {
+        Some(path) => Schema::load(&path).map(Some),
+        None => Ok(None)
+    }
+}
+
+/// Passport fields as `(name, value)` pairs, in input order.
+pub type Passport = Vec<(String, String)>;
+
+/// Reads `name:value` records separated by blank lines, passports or
+/// anything else a [`Schema`] can check, with the 1-based lines each spans.
+pub fn records(input: &str) -> Result<Vec<(Passport, RangeInclusive<usize>)>, ParseError> {
+    let lines: Vec<&str> = input.lines().collect();
+
+    let mut records = Vec::new();
+    let mut first = 1;
+
+    for passport_lines in lines.split(|line| line.trim().is_empty()) {
+        let fields: Result<Vec<_>, _> = passport_lines.iter()
+            .flat_map(|line| line.split_whitespace())
+            .map(|field| {
+                let fields_str: SmallVec<[&str; 2]> = field.splitn(2, ":").collect();
+
+                if let &[field_name, field_value] = &fields_str[..] {
+                    Ok((field_name.to_string(), field_value.to_string()))
+
+                } else {
+                    Err(ParseError::new(field, "a `name:value` field"))
+
+                }
+            })
+            .collect();
+
+        let fields = fields?;
+
+        if !fields.is_empty() {
+            records.push((fields, first..=first + passport_lines.len() - 1));
+        }
+
+        // Each group of lines is followed by the blank line it was split at.
+        first += passport_lines.len() + 1;
+    }
+
+    Ok(records)
+}
+
+pub fn passports(input: &str) -> Result<Vec<Passport>, ParseError> {
+    Ok(records(input)?.into_iter().map(|(fields, _)| fields).collect())
+}
+
+/// Checks a single passport field value, `None` if it cannot be parsed at all.
+pub fn validate(field: &str, value: &str) -> Option<bool> {
+    passport().check(field, value)
+}
+
+/// Passports having all the required fields, `cid` being optional.
+pub fn valid_field_set(passports: &[Passport]) -> impl Iterator<Item=&Passport> {
+    passports.iter().filter(|fields| passport().complete(fields))
+}
+
+/// Passports having all the required fields, each of them valid.
+pub fn valid_passports(passports: &[Passport]) -> impl Iterator<Item=&Passport> {
+    passports.iter().filter(|fields| passport().accepts(fields))
+}
+
+/// What is wrong with a passport, and where it is.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Diagnosis {
+    /// 1-based position among the passports.
+    pub passport: usize,
+    pub lines: RangeInclusive<usize>,
+    pub issues: Vec<Issue>
+}
+
+impl fmt::Display for Diagnosis {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "passport {} (lines {}-{}):", self.passport, self.lines.start(), self.lines.end())?;
+
+        for issue in self.issues.iter() {
+            write!(f, "\n  {}", issue)?;
+        }
+
+        Ok(())
+    }
+}
+
+/// Passports `schema` finds something wrong with; part one only looks for
+/// missing and repeated fields, part two checks the values too.
+pub fn diagnose(records: &[(Passport, RangeInclusive<usize>)], schema: &Schema, part: Part) -> Vec<Diagnosis> {
+    records.iter().zip(1..)
+        .map(|((fields, lines), passport)| {
+            let issues = schema.diagnose(fields, part == Part::Two);
+            Diagnosis { passport, lines: lines.clone(), issues }
+        })
+        .filter(|d| !d.issues.is_empty())
+        .collect()
+}
+
+fn json_diagnosis(d: &Diagnosis) -> String {
+    let issues: Vec<String> = d.issues.iter()
+        .map(|i| format!(
+            r#"{{"field": {}, "value": {}, "problem": "{}", "message": {}}}"#,
+            json_string(&i.field), i.value.as_deref().map_or("null".to_string(), json_string), i.problem.name(), json_string(&i.message)
+        ))
+        .collect();
+
+    format!(
+        r#"{{"passport": {}, "lines": [{}, {}], "issues": [{}]}}"#,
+        d.passport, d.lines.start(), d.lines.end(), issues.join(", ")
+    )
+}
+
+/// Lists the passports that fail `part` of `input` and why, as text or a JSON array.
+pub fn report(part: Part, input: &str, format: Format) -> Result<String, ParseError> {
+    report_against(passport(), part, input, format)
+}
+
+/// Like [`report`], checking the records of `input` against `schema`.
+pub fn report_against(schema: &Schema, part: Part, input: &str, format: Format) -> Result<String, ParseError> {
+    let records = records(input).map_err(|err| err.locate(Day4::DAY, input))?;
+    let found = diagnose(&records, schema, part);
+
+    let entries: Vec<String> = match format {
+        Format::Json => found.iter().map(json_diagnosis).collect(),
+        _ => found.iter().map(Diagnosis::to_string).collect()
+    };
+
+    Ok(match format {
+        Format::Json if entries.is_empty() => "[]\n".to_string(),
+        Format::Json => format!("[\n  {}\n]\n", entries.join(",\n  ")),
+        _ => entries.iter().map(|e| format!("{}\n", e)).collect()
+    })
+}
+
+/// Solves `part` of `input` checking passports against `schema` instead of
+/// the bundled one.
+pub fn solve_with(schema: &Schema, part: Part, input: &str) -> Result<Option<Answer>, ParseError> {
+    let passports = solver::parse::<Day4>(input)?;
+
+    let valid = match part {
+        Part::One => passports.iter().filter(|fields| schema.complete(fields)).count(),
+        Part::Two => passports.iter().filter(|fields| schema.accepts(fields)).count()
+    };
+
+    Ok(Some(valid.into()))
+}
+
+pub struct Day4;
+
+impl Solver for Day4 {
+    const DAY: usize = 4;
+
+    type Input = Vec<Passport>;
+    type One = usize;
+    type Two = usize;
+
+    fn parse(input: &str) -> Result<Vec<Passport>, ParseError> {
+        passports(input)
+    }
+
+    fn part_one(passports: &Vec<Passport>) -> usize {
+        valid_field_set(passports).count()
+    }
+
+    fn part_two(passports: &Vec<Passport>) -> usize {
+        valid_passports(passports).count()
+    }
+}
+
+/// Examples from the puzzle text.
+pub const EXAMPLE_1: &str = r"ecl:gry pid:860033327 eyr:2020 hcl:#fffffd
+byr:1937 iyr:2017 cid:147 hgt:183cm
+
+iyr:2013 ecl:amb cid:350 eyr:2023 pid:028048884
+hcl:#cfa07d byr:1929
+
+hcl:#ae17e1 iyr:2013
+eyr:2024
+ecl:brn pid:760753108 byr:1931
+hgt:179cm
+
+hcl:#cfa07d eyr:2025 pid:166559648
+iyr:2011 ecl:brn hgt:59in";
+pub const EXAMPLE_2: &str = r"eyr:1972 cid:100
+hcl:#18171d ecl:amb hgt:170 pid:186cm iyr:2018 byr:1926
+
+iyr:2019
+hcl:#602927 eyr:1967 hgt:170cm
+ecl:grn pid:012533040 byr:1946
+
+hcl:dab227 iyr:2012
+ecl:brn hgt:182cm pid:021572410 eyr:2020 byr:1992 cid:277
+
+hgt:59cm ecl:zzz
+eyr:2038 hcl:74454a iyr:2023
+pid:3556412378 byr:2007
+";
+pub const EXAMPLE_3: &str = r"pid:087499704 hgt:74in ecl:grn iyr:2012 eyr:2030 byr:1980
+hcl:#623a2f
+
+eyr:2029 ecl:blu cid:129 byr:1989
+iyr:2014 pid:896056539 hcl:#a97842 hgt:165cm
+
+hcl:#888785
+hgt:164cm byr:2001 iyr:2015 cid:88
+pid:545766238 ecl:hzl
+eyr:2022
+
+iyr:2010 hgt:158cm hcl:#b6652a ecl:blu byr:1944 eyr:2021 pid:093154719";
+
+#[test]
+fn example_1() {
+    let passports = Day4::parse(EXAMPLE_1).expect("correct parse");
+    assert_eq!(Day4::part_one(&passports), 2);
+}
+
+#[test]
+fn example_2() {
+    let passports = Day4::parse(EXAMPLE_2).expect("correct parse");
+    assert_eq!(Day4::part_two(&passports), 0);
+}
+
+#[test]
+fn example_3() {
+    let passports = Day4::parse(EXAMPLE_3).expect("correct parse");
+    assert_eq!(Day4::part_two(&passports), 4);
+}
+
+#[test]
+fn fields() {
+    assert_eq!(validate("byr", "2002"), Some(true));
+    assert_eq!(validate("byr", "2003"), Some(false));
+    assert_eq!(validate("hgt", "190in"), Some(false));
+    assert_eq!(validate("hgt", "190"), None);
+    assert_eq!(validate("hcl", "#123abz"), Some(false));
+    assert_eq!(validate("ecl", "wat"), Some(false));
+    assert_eq!(validate("pid", "0123456789"), Some(false));
+    assert_eq!(validate("cid", "anything"), Some(true));
+    assert!(!passport().field("cid").expect("listed").required);
+}
+
+#[test]
+fn other_schema() {
+    let schema = Schema::parse("[fields.qty]\ntype = \"range\"\nmin = 1\nmax = 9\nmessage = \"quantity must be a digit\"").unwrap();
+    let text = report_against(&schema, Part::Two, "qty:3\n\nqty:12 byr:1937", Format::Text).unwrap();
+    assert_eq!(text, "passport 2 (lines 3-3):\n  qty: out of range, `12`: quantity must be a digit\n");
+}
+
+#[test]
+fn diagnostics() {
+    let example = records(EXAMPLE_2).expect("correct parse");
+    assert_eq!(example.iter().map(|(_, lines)| lines.clone()).collect::<Vec<_>>(), [1..=2, 4..=6, 8..=9, 11..=13]);
+
+    let found = diagnose(&example, passport(), Part::Two);
+    assert_eq!(found.len(), 4);
+    assert_eq!(found[0].to_string(), "passport 1 (lines 1-2):
+  eyr: out of range, `1972`: expiration year must be from 2020 to 2030
+  hgt: unknown unit, `170`: height must be 150 to 193cm or 59 to 76in
+  pid: does not match the pattern, `186cm`: passport id must be nine digits");
+
+    let missing = diagnose(&example, passport(), Part::One);
+    assert!(missing.is_empty());
+
+    let repeated = records("byr:1937 byr:1940\n\necl:grn").unwrap();
+    let found = diagnose(&repeated, passport(), Part::One);
+    assert_eq!(found[0].issues.len(), 7);
+    assert_eq!(found[0].issues[6].problem, crate::schema::Problem::Duplicate);
+    assert_eq!(found[1].lines, 3..=3);
+
+    let json = report(Part::One, "byr:1937 byr:1940\n\necl:grn", Format::Json).unwrap();
+    assert!(json.starts_with(r#"[
+  {"passport": 1, "lines": [1, 1], "issues": [{"field": "ecl", "value": null, "problem": "missing", "message": "eye colour"#));
+}
+
+#[test]
+fn schema_next_to_input() {
+    let dir = std::env::temp_dir().join("aoc2020-passport-schema");
+    std::fs::create_dir_all(&dir).unwrap();
+    std::fs::write(dir.join(SCHEMA_FILE), "[fields.byr]\ntype = \"range\"\nmin = 1950\nmax = 2002\nmessage = \"born 1950 to 2002\"").unwrap();
+
+    let schema = find_schema(&Source::Dir(dir.clone())).unwrap().expect("found");
+    assert_eq!(solve_with(&schema, Part::Two, "byr:1937\n\nbyr:1960"), Ok(Some(1usize.into())));
+    assert_eq!(find_schema(&Source::Path(dir.join("4.txt"))).unwrap().map(|s| s.fields.len()), Some(1));
+
+    std::fs::write(dir.join(SCHEMA_FILE), "[fields").unwrap();
+    assert!(find_schema(&Source::Dir(dir.clone())).is_err());
+
+    std::fs::remove_dir_all(&dir).unwrap();
+    assert!(find_schema(&Source::Dir(dir)).unwrap().is_none());
+}
@@ -0,0 +1,243 @@
+//! Day 5: Binary Boarding.
+
+use std::io::BufRead;
+use std::ops::RangeInclusive;
+
+use crate::Part;
+use crate::error::ParseError;
+use crate::grid::Grid;
+use crate::report::{Format, json_string};
+use crate::solver::{self, Answer, Solver};
+use crate::stream::{self, StreamError};
+
+fn strbin(one: char, s: &str) -> usize {
+    s.chars().fold(0, |acc, c| acc + acc + (one == c) as usize)
+}
+
+/// How boarding passes are written on a plane: a bit per letter, first the
+/// row with `F` for 0 and `B` for 1, then the column with `L` and `R`.
+/// Seat ids put the row bits in front of the column bits.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub struct SeatCode {
+    pub row_bits: u32,
+    pub column_bits: u32
+}
+
+impl SeatCode {
+    /// The plane of the puzzle, 128 rows of 8 seats.
+    pub const PLANE: SeatCode = SeatCode { row_bits: 7, column_bits: 3 };
+
+    pub fn rows(self) -> usize {
+        1 << self.row_bits
+    }
+
+    pub fn columns(self) -> usize {
+        1 << self.column_bits
+    }
+
+    pub fn seats(self) -> usize {
+        self.rows() * self.columns()
+    }
+
+    pub fn id(self, (row, column): (usize, usize)) -> usize {
+        row << self.column_bits | column
+    }
+
+    pub fn seat(self, id: usize) -> (usize, usize) {
+        (id >> self.column_bits, id & (self.columns() - 1))
+    }
+
+    fn is_pass(self, s: &str) -> bool {
+        let rows = self.row_bits as usize;
+
+        if !s.is_ascii() || s.len() != rows + self.column_bits as usize {
+            return false;
+        }
+
+        let (row_str, column_str) = s.split_at(rows);
+        row_str.chars().all(|c| c == 'F' || c == 'B')
+            && column_str.chars().all(|c| c == 'L' || c == 'R')
+    }
+
+    /// Reads a boarding pass like `FBFBBFFRLR` as `(row, column)`.
+    pub fn decode(self, s: &str) -> Result<(usize, usize), ParseError> {
+        if !self.is_pass(s) {
+            let example = self.encode((self.rows() / 3, self.columns() / 3 * 2)).expect("on the plane");
+            return Err(ParseError::new(s, format!("a boarding pass like `{}`", example)));
+        }
+
+        let (row_str, column_str) = s.split_at(self.row_bits as usize);
+        Ok((strbin('B', row_str), strbin('R', column_str)))
+    }
+
+    /// The boarding pass of a seat, `None` if it is not on the plane.
+    pub fn encode(self, (row, column): (usize, usize)) -> Option<String> {
+        if row >= self.rows() || column >= self.columns() {
+            return None;
+        }
+
+        let bits = |n: usize, width: u32, zero, one| (0..width).rev().map(move |b| if n >> b & 1 == 1 { one } else { zero });
+        Some(bits(row, self.row_bits, 'F', 'B').chain(bits(column, self.column_bits, 'L', 'R')).collect())
+    }
+
+    /// Which seats are taken, by id; the ids have to be on the plane.
+    pub fn taken(self, ids: impl IntoIterator<Item=usize>) -> Vec<bool> {
+        let mut taken = vec![false; self.seats()];
+
+        for id in ids {
+            taken[id] = true;
+        }
+
+        taken
+    }
+
+    /// Every row of the plane, a column per seat, `#` if it is taken.
+    pub fn seat_map(self, taken: &[bool]) -> Grid<bool> {
+        Grid::from_fn(self.columns(), self.rows(), |(column, row)| taken[self.id((row, column))])
+    }
+}
+
+/// Decodes a boarding pass like `FBFBBFFRLR` into `(row, column)`.
+pub fn seatdecode(s: &str) -> (usize, usize) {
+    SeatCode::PLANE.decode(s).expect("a boarding pass")
+}
+
+fn seat_id(s: &str) -> Result<usize, ParseError> {
+    SeatCode::PLANE.decode(s).map(|seat| SeatCode::PLANE.id(seat))
+}
+
+fn seat_ids(input: &str) -> Result<Vec<usize>, ParseError> {
+    input.lines().map(str::trim).map(seat_id).collect()
+}
+
+/// Runs of free seats between the first and last taken ones, by id.
+pub fn gaps(taken: &[bool]) -> Vec<RangeInclusive<usize>> {
+    let first = taken.iter().position(|&t| t).unwrap_or(taken.len());
+    let mut gaps = Vec::new();
+    let mut start = None;
+
+    for (id, &t) in taken.iter().enumerate().skip(first) {
+        match (t, start) {
+            (false, None) => { start = Some(id); },
+            (true, Some(s)) => {
+                gaps.push(s..=id - 1);
+                start = None;
+            },
+            _ => {}
+        }
+    }
+
+    gaps
+}
+
+/// A free seat with taken ones on either side.
+fn lone_gap(taken: &[bool]) -> Option<usize> {
+    gaps(taken).into_iter().find(|gap| gap.start() == gap.end()).map(|gap| *gap.start())
+}
+
+/// Highest taken seat id and a free seat between two taken ones, reading
+/// boarding passes one per line into a map of every seat on the plane.
+pub fn max_and_missing_stream(reader: impl BufRead) -> Result<(Option<usize>, Option<usize>), StreamError> {
+    let mut taken = vec![false; SeatCode::PLANE.seats()];
+
+    for id in stream::parse_lines(Day5::DAY, reader, seat_id) {
+        taken[id?] = true;
+    }
+
+    Ok((taken.iter().rposition(|&t| t), lone_gap(&taken)))
+}
+
+/// Solves `part` reading the input from `reader` in bounded memory.
+pub fn stream(part: Part, reader: &mut dyn BufRead) -> Result<Option<Answer>, StreamError> {
+    let (max, missing) = max_and_missing_stream(reader)?;
+
+    let answer = match part {
+        Part::One => max,
+        Part::Two => missing
+    };
+
+    Ok(Some(answer.expect("exists").into()))
+}
+
+/// The seat map followed by every gap, as text or JSON; the same for both parts.
+pub fn report(_: Part, input: &str, format: Format) -> Result<String, ParseError> {
+    let code = SeatCode::PLANE;
+    let taken = code.taken(solver::parse::<Day5>(input)?);
+    let map = code.seat_map(&taken);
+    let gaps = gaps(&taken);
+    let pass = |id| code.encode(code.seat(id)).expect("on the plane");
+
+    Ok(match format {
+        Format::Json => {
+            let rows: Vec<String> = map.to_string().lines().map(json_string).collect();
+            let gaps: Vec<String> = gaps.iter()
+                .map(|g| format!(r#"{{"first": {}, "last": {}, "passes": [{}, {}]}}"#, g.start(), g.end(), json_string(&pass(*g.start())), json_string(&pass(*g.end()))))
+                .collect();
+
+            format!("{{\"map\": [{}],\n \"gaps\": [{}]}}\n", rows.join(", "), gaps.join(", "))
+        },
+        _ => {
+            let gaps: String = gaps.iter()
+                .map(|g| match (*g.start(), *g.end()) {
+                    (id, last) if id == last => format!("gap {}: {}\n", id, pass(id)),
+                    (first, last) => format!("gap {}-{}: {} to {}\n", first, last, pass(first), pass(last))
+                })
+                .collect();
+
+            format!("{}\n{}", map, gaps)
+        }
+    })
+}
+
+pub struct Day5;
+
+impl Solver for Day5 {
+    const DAY: usize = 5;
+
+    type Input = Vec<usize>;
+    type One = usize;
+    type Two = usize;
+
+    fn parse(input: &str) -> Result<Vec<usize>, ParseError> {
+        seat_ids(input)
+    }
+
+    fn part_one(seat_ids: &Vec<usize>) -> usize {
+        seat_ids.iter().copied().max().expect("exists")
+    }
+
+    fn part_two(seat_ids: &Vec<usize>) -> usize {
+        lone_gap(&SeatCode::PLANE.taken(seat_ids.iter().copied())).expect("exists")
+    }
+}
+
+/// Boarding passes from the puzzle text, one seat left free between them.
+pub const EXAMPLE: &str = "FBFBBFFRLR\nFBFBBFFRRR\nBFFFBBFRRR";
+
+#[test]
+fn examples() {
+    assert_eq!(seatdecode("FBFBBFFRLR"), (44, 5));
+    assert_eq!(seatdecode("BFFFBBFRRR"), (70, 7));
+
+    assert_eq!(max_and_missing_stream(EXAMPLE.as_bytes()).unwrap(), (Some(567), Some(358)));
+}
+
+#[test]
+fn seat_codes() {
+    assert_eq!(SeatCode::PLANE.encode((44, 5)).as_deref(), Some("FBFBBFFRLR"));
+    assert_eq!(SeatCode::PLANE.encode((128, 0)), None);
+    assert_eq!(SeatCode::PLANE.decode("FBFBBFFRL").unwrap_err().expected, "a boarding pass like `FBFBFBFRLL`");
+    assert_eq!(SeatCode::PLANE.decode("FBFBBF€RLR").unwrap_err().text, "FBFBBF€RLR");
+    assert!(max_and_missing_stream("FBFBBFFRLR\nFBFBBF€RLR".as_bytes()).is_err());
+
+    let small = SeatCode { row_bits: 2, column_bits: 2 };
+    assert_eq!(small.decode("BFRL"), Ok((2, 2)));
+    assert_eq!(small.encode((2, 2)).as_deref(), Some("BFRL"));
+    assert_eq!(small.id((2, 2)), 10);
+    assert_eq!(small.seat(10), (2, 2));
+
+    let taken = small.taken(vec![1, 2, 6, 7, 9, 13]);
+    assert_eq!(small.seat_map(&taken).to_string(), ".##.\n..##\n.#..\n.#..\n");
+    assert_eq!(gaps(&taken), [3..=5, 8..=8, 10..=12]);
+    assert_eq!(lone_gap(&taken), Some(8));
+}
@@ -0,0 +1,383 @@
+//! Day 6: Custom Customs.
+//!
+//! Besides the puzzle's two counts, groups can be queried with set algebra:
+//! `any` and `all` are the questions anyone or everyone answered, `odd` the
+//! ones answered by an odd number of people, `>=2`, `<=1` or `=1` the ones
+//! answered by that many, and `#3` the answers of the third person. Sets
+//! combine left to right with `&`, `|`, `^` and `-`, `!` takes every other
+//! question, and parentheses group: `=1 & !#1` is what only one person
+//! answered, and not the first one.
+
+use std::fmt;
+use std::ops::{BitAnd, BitOr, BitXor, Not};
+use std::str::FromStr;
+
+use crate::error::{ParseError, only_chars, parse_as};
+use crate::solver::Solver;
+
+const QUESTIONS: usize = 26;
+
+/// A set of questions from `a` to `z`, a bit each.
+#[derive(Clone, Copy, Debug, Default, Eq, Hash, PartialEq)]
+pub struct Answers(u32);
+
+impl Answers {
+    pub const ALL: Answers = Answers((1 << QUESTIONS) - 1);
+
+    /// The questions in `s`, ignoring anything but `a` to `z`.
+    pub fn of(s: &str) -> Self {
+        Answers(s.bytes().filter(u8::is_ascii_lowercase).fold(0, |set, b| set | 1 << (b - b'a')))
+    }
+
+    pub fn len(self) -> usize {
+        self.0.count_ones() as usize
+    }
+
+    pub fn is_empty(self) -> bool {
+        self.0 == 0
+    }
+
+    pub fn contains(self, question: char) -> bool {
+        question.is_ascii_lowercase() && self.0 & 1 << (question as u32 - 'a' as u32) != 0
+    }
+
+    pub fn questions(self) -> impl Iterator<Item=char> {
+        (b'a'..=b'z').map(char::from).filter(move |&q| self.contains(q))
+    }
+}
+
+impl BitAnd for Answers {
+    type Output = Answers;
+
+    fn bitand(self, other: Answers) -> Answers {
+        Answers(self.0 & other.0)
+    }
+}
+
+impl BitOr for Answers {
+    type Output = Answers;
+
+    fn bitor(self, other: Answers) -> Answers {
+        Answers(self.0 | other.0)
+    }
+}
+
+impl BitXor for Answers {
+    type Output = Answers;
+
+    fn bitxor(self, other: Answers) -> Answers {
+        Answers(self.0 ^ other.0)
+    }
+}
+
+impl Not for Answers {
+    type Output = Answers;
+
+    fn not(self) -> Answers {
+        Answers(!self.0 & Answers::ALL.0)
+    }
+}
+
+impl fmt::Display for Answers {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.questions().collect::<String>())
+    }
+}
+
+/// Answers of one group, a line per person.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Group {
+    pub members: Vec<String>,
+    answers: Vec<Answers>
+}
+
+impl Group {
+    pub fn new(members: Vec<String>) -> Self {
+        let answers = members.iter().map(|m| Answers::of(m)).collect();
+        Group { members, answers }
+    }
+
+    /// What each member answered, in order.
+    pub fn answers(&self) -> &[Answers] {
+        &self.answers
+    }
+
+    /// Questions anyone answered.
+    pub fn any(&self) -> Answers {
+        self.answers.iter().fold(Answers::default(), |set, &a| set | a)
+    }
+
+    /// Questions everyone answered.
+    pub fn all(&self) -> Answers {
+        self.answers.iter().fold(Answers::ALL, |set, &a| set & a)
+    }
+
+    /// Questions an odd number of members answered.
+    pub fn odd(&self) -> Answers {
+        self.answers.iter().fold(Answers::default(), |set, &a| set ^ a)
+    }
+
+    /// How many members answered each question, `a` first.
+    pub fn histogram(&self) -> [usize; QUESTIONS] {
+        let mut counts = [0; QUESTIONS];
+
+        for answers in self.answers.iter() {
+            for (q, count) in counts.iter_mut().enumerate() {
+                *count += (answers.0 >> q & 1) as usize;
+            }
+        }
+
+        counts
+    }
+
+    /// Questions answered by a number of members `wanted` accepts.
+    pub fn answered_by(&self, wanted: impl Fn(usize) -> bool) -> Answers {
+        let counts = self.histogram();
+        Answers((0..QUESTIONS).filter(|&q| wanted(counts[q])).fold(0, |set, q| set | 1 << q))
+    }
+
+    /// The member disagreeing most with the majority, answering questions
+    /// most others did not or leaving out ones most others answered, or
+    /// `None` if no single member stands out.
+    pub fn odd_one_out(&self) -> Option<usize> {
+        let majority = self.answered_by(|n| 2 * n > self.members.len());
+        let distance = |i: usize| (self.answers[i] ^ majority).len();
+
+        let odd = (0..self.members.len()).max_by_key(|&i| distance(i))?;
+        let ties = (0..self.members.len()).filter(|&i| distance(i) == distance(odd)).count();
+
+        Some(odd).filter(|_| ties == 1 && distance(odd) > 0)
+    }
+}
+
+/// How many members answered each question across all `groups`.
+pub fn histogram(groups: &[Group]) -> [usize; QUESTIONS] {
+    groups.iter().fold([0; QUESTIONS], |mut total, group| {
+        for (t, n) in total.iter_mut().zip(group.histogram().iter()) {
+            *t += n;
+        }
+
+        total
+    })
+}
+
+/// A set of questions worked out for each group, see the module docs.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum Query {
+    Any,
+    All,
+    Odd,
+    AtLeast(usize),
+    AtMost(usize),
+    Exactly(usize),
+    /// Answers of the member with this 1-based number, none if there is no such member.
+    Member(usize),
+    Not(Box<Query>),
+    And(Box<Query>, Box<Query>),
+    Or(Box<Query>, Box<Query>),
+    Xor(Box<Query>, Box<Query>),
+    Minus(Box<Query>, Box<Query>)
+}
+
+impl Query {
+    pub fn eval(&self, group: &Group) -> Answers {
+        match self {
+            Query::Any => group.any(),
+            Query::All => group.all(),
+            Query::Odd => group.odd(),
+            Query::AtLeast(k) => group.answered_by(|n| n >= *k),
+            Query::AtMost(k) => group.answered_by(|n| n <= *k),
+            Query::Exactly(k) => group.answered_by(|n| n == *k),
+            Query::Member(i) => i.checked_sub(1).and_then(|i| group.answers.get(i)).copied().unwrap_or_default(),
+            Query::Not(q) => !q.eval(group),
+            Query::And(a, b) => a.eval(group) & b.eval(group),
+            Query::Or(a, b) => a.eval(group) | b.eval(group),
+            Query::Xor(a, b) => a.eval(group) ^ b.eval(group),
+            Query::Minus(a, b) => a.eval(group) & !b.eval(group)
+        }
+    }
+
+    /// Questions in the set, summed over `groups`.
+    pub fn count(&self, groups: &[Group]) -> usize {
+        groups.iter().map(|g| self.eval(g).len()).sum()
+    }
+}
+
+type Parsed<'a, T> = Result<(T, &'a str), ParseError>;
+
+type Counted = fn(usize) -> Query;
+
+/// Sets written as a prefix and a number.
+const COUNTED: [(&str, Counted); 4] = [
+    (">=", Query::AtLeast),
+    ("<=", Query::AtMost),
+    ("=", Query::Exactly),
+    ("#", Query::Member)
+];
+
+fn parse_number(input: &str) -> Parsed<'_, usize> {
+    let end = input.find(|c: char| !c.is_ascii_digit()).unwrap_or(input.len());
+    let (number, tail) = input.split_at(end);
+
+    Ok((parse_as(number, "a number")?, tail))
+}
+
+fn parse_atom(input: &str) -> Parsed<'_, Query> {
+    let input = input.trim_start();
+    let word = |w: &str, q: Query| input.strip_prefix(w).map(|tail| (q, tail));
+
+    if let Some(tail) = input.strip_prefix('(') {
+        let (query, tail) = parse_expr(tail)?;
+        let tail = tail.trim_start();
+        let tail = tail.strip_prefix(')').ok_or_else(|| ParseError::new(tail, "an operator or `)`"))?;
+        return Ok((query, tail));
+    }
+
+    if let Some(tail) = input.strip_prefix('!') {
+        let (query, tail) = parse_atom(tail)?;
+        return Ok((Query::Not(Box::new(query)), tail));
+    }
+
+    for &(prefix, query) in COUNTED.iter() {
+        if let Some(tail) = input.strip_prefix(prefix) {
+            let (n, tail) = parse_number(tail)?;
+            return Ok((query(n), tail));
+        }
+    }
+
+    word("any", Query::Any)
+        .or_else(|| word("all", Query::All))
+        .or_else(|| word("odd", Query::Odd))
+        .ok_or_else(|| ParseError::new(input, "a set like `any`, `>=2` or `#1`"))
+}
+
+fn parse_expr(input: &str) -> Parsed<'_, Query> {
+    let (mut query, mut tail) = parse_atom(input)?;
+
+    loop {
+        let rest = tail.trim_start();
+        let op: fn(Box<Query>, Box<Query>) -> Query = match rest.chars().next() {
+            Some('&') => Query::And,
+            Some('|') => Query::Or,
+            Some('^') => Query::Xor,
+            Some('-') => Query::Minus,
+            _ => { return Ok((query, tail)); }
+        };
+
+        let (rhs, rhs_tail) = parse_atom(&rest[1..])?;
+        query = op(Box::new(query), Box::new(rhs));
+        tail = rhs_tail;
+    }
+}
+
+impl FromStr for Query {
+    type Err = ParseError;
+
+    fn from_str(s: &str) -> Result<Self, ParseError> {
+        let (query, tail) = parse_expr(s)?;
+        let tail = tail.trim_start();
+
+        if tail.is_empty() {
+            Ok(query)
+        } else {
+            Err(ParseError::new(tail, "an operator"))
+        }
+    }
+}
+
+fn groups(input: &str) -> Result<Vec<Group>, ParseError> {
+    let lines: Vec<_> = input.lines().map(str::trim).collect();
+
+    for line in lines.iter() {
+        only_chars(line, "abcdefghijklmnopqrstuvwxyz", "a question from `a` to `z`")?;
+    }
+
+    Ok(lines.split(|line| line.is_empty())
+        .filter(|group| !group.is_empty())
+        .map(|group| Group::new(group.iter().map(|&s| s.to_string()).collect()))
+        .collect())
+}
+
+/// Questions anyone in the group answered "yes" to.
+pub fn char_union<F: AsRef<str>>(group: &[F]) -> usize {
+    group.iter().fold(Answers::default(), |set, s| set | Answers::of(s.as_ref())).len()
+}
+
+/// Questions everyone in the group answered "yes" to.
+pub fn char_intersect<F: AsRef<str>>(group: &[F]) -> usize {
+    group.iter().fold(Answers::ALL, |set, s| set & Answers::of(s.as_ref())).len()
+}
+
+pub struct Day6;
+
+impl Solver for Day6 {
+    const DAY: usize = 6;
+
+    type Input = Vec<Group>;
+    type One = usize;
+    type Two = usize;
+
+    fn parse(input: &str) -> Result<Vec<Group>, ParseError> {
+        groups(input)
+    }
+
+    fn part_one(groups: &Vec<Group>) -> usize {
+        Query::Any.count(groups)
+    }
+
+    fn part_two(groups: &Vec<Group>) -> usize {
+        Query::All.count(groups)
+    }
+}
+
+/// Example from the puzzle text.
+pub const EXAMPLE: &str = r"abc
+
+a
+b
+c
+
+ab
+ac
+
+a
+a
+a
+a
+
+b";
+
+#[test]
+fn example() {
+    let groups = Day6::parse(EXAMPLE).expect("correct parse");
+    assert_eq!(Day6::part_one(&groups), 11);
+    assert_eq!(Day6::part_two(&groups), 6);
+    assert_eq!((char_union(&["ab", "ac"]), char_intersect(&["ab", "ac"])), (3, 1));
+}
+
+#[test]
+fn queries() {
+    let groups = Day6::parse("abc\nabd\nabc\n\nab\nab\nab\nzab").unwrap();
+    let count = |q: &str| q.parse::<Query>().expect("valid query").count(&groups);
+
+    assert_eq!(count("any"), 4 + 3);
+    assert_eq!(count(">=2"), 3 + 2);
+    assert_eq!(count("=1"), 1 + 1);
+    assert_eq!(count("odd"), 3 + 1);
+    assert_eq!(count("any ^ all"), 2 + 1);
+    assert_eq!(count("=1 & !#1"), 1 + 1);
+    assert_eq!(count("(any - #2) | all"), 3 + 3);
+    assert_eq!(count("#9"), 0);
+
+    assert_eq!(groups[0].histogram()[..4], [3, 3, 2, 1]);
+    assert_eq!((histogram(&groups)[0], histogram(&groups)[25]), (7, 1));
+    assert_eq!(groups[0].odd_one_out(), Some(1));
+    assert_eq!(groups[0].members[1], "abd");
+    assert_eq!(groups[1].odd_one_out(), Some(3));
+    assert_eq!(Group::new(vec!["ab".to_string(), "cd".to_string()]).odd_one_out(), None);
+
+    assert_eq!("any &".parse::<Query>().unwrap_err().text, "");
+    assert_eq!("any all".parse::<Query>().unwrap_err().text, "all");
+    assert_eq!("(any".parse::<Query>().unwrap_err().text, "");
+    assert!(Day6::parse("ab\nA").is_err());
+}
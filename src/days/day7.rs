@@ -0,0 +1,465 @@
+//! Day 7: Handy Haversacks.
+
+use std::collections::{HashMap, VecDeque};
+use std::fmt;
+
+use crate::Part;
+use crate::error::{ParseError, parse_as};
+use crate::report::{Format, json_string};
+use crate::solver::{self, Solver};
+
+const CONTAIN: &str = " bags contain ";
+
+/// Each bag colour with the counts and colours of the bags it directly contains.
+pub type Rules = Vec<(String, Vec<(usize, String)>)>;
+
+pub fn rules(input: &str) -> Result<Rules, ParseError> {
+    input.lines()
+        .map(|line| {
+            let contain_pos = line.find(CONTAIN).ok_or_else(|| ParseError::new(line, "`<colour> bags contain <bags>.`"))?;
+            let outer = &line[..contain_pos];
+            let inner = &line[(contain_pos + CONTAIN.len())..];
+
+            let inner = if inner == "no other bags." {
+                Vec::new()
+            } else {
+                let inner: Result<Vec<_>, _> = inner.strip_suffix(".").ok_or_else(|| ParseError::new(inner, "bags ending with `.`"))?.split(", ")
+                    .map(|bag| {
+                        let pair = bag.strip_suffix(" bag").or_else(|| bag.strip_suffix(" bags"))
+                            .ok_or_else(|| ParseError::new(bag, "`<count> <colour> bags`"))?;
+                        let (cnt, name) = pair.split_once(" ").ok_or_else(|| ParseError::new(pair, "`<count> <colour>`"))?;
+                        let cnt = parse_as(cnt, "a count")?;
+                        Ok((cnt, name.to_string()))
+                    })
+                    .collect();
+
+                inner?
+            };
+
+            Ok((outer.to_string(), inner))
+        })
+        .collect()
+}
+
+/// A colour as a Graphviz id.
+fn dot_id(name: &str) -> String {
+    format!("\"{}\"", name.replace('\\', "\\\\").replace('"', "\\\""))
+}
+
+/// A bag colour, numbered in order of first mention.
+pub type Bag = usize;
+
+/// Bags that end up inside themselves, each containing the next and the
+/// last one the first.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Cycle {
+    pub bags: Vec<String>
+}
+
+impl fmt::Display for Cycle {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for bag in self.bags.iter() {
+            write!(f, "{} -> ", bag)?;
+        }
+
+        write!(f, "{}", self.bags[0])
+    }
+}
+
+impl std::error::Error for Cycle {}
+
+/// Rules that cannot be made into a [`Graph`].
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum GraphError {
+    Cycle(Cycle),
+    /// A bag holding more bags than a `usize` can count.
+    Overflow { bag: String }
+}
+
+impl fmt::Display for GraphError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            GraphError::Cycle(cycle) => write!(f, "{}", cycle),
+            GraphError::Overflow { bag } => write!(f, "too many bags inside {}", bag)
+        }
+    }
+}
+
+impl std::error::Error for GraphError {}
+
+/// The rules as a graph of numbered bags, with edges both ways, the bags
+/// in topological order and the size of each bag worked out up front.
+#[derive(Clone, Debug)]
+pub struct Graph {
+    names: Vec<String>,
+    index: HashMap<String, Bag>,
+    contents: Vec<Vec<(usize, Bag)>>,
+    containers: Vec<Vec<(usize, Bag)>>,
+    order: Vec<Bag>,
+    totals: Vec<usize>
+}
+
+impl Graph {
+    /// Bags without a rule of their own contain nothing.
+    pub fn new(rules: &Rules) -> Result<Self, GraphError> {
+        let mut graph = Graph {
+            names: Vec::new(),
+            index: HashMap::new(),
+            contents: Vec::new(),
+            containers: Vec::new(),
+            order: Vec::new(),
+            totals: Vec::new()
+        };
+
+        for (outer, inners) in rules {
+            let outer = graph.intern(outer);
+
+            for (count, inner) in inners {
+                let inner = graph.intern(inner);
+                graph.contents[outer].push((*count, inner));
+                graph.containers[inner].push((*count, outer));
+            }
+        }
+
+        graph.order = graph.topological_order().map_err(GraphError::Cycle)?;
+        graph.totals = vec![1; graph.len()];
+
+        for &bag in graph.order.iter().rev() {
+            let total = graph.contents[bag].iter()
+                .try_fold(1usize, |total, &(count, inner)| count.checked_mul(graph.totals[inner])?.checked_add(total));
+
+            graph.totals[bag] = total.ok_or_else(|| GraphError::Overflow { bag: graph.names[bag].clone() })?;
+        }
+
+        Ok(graph)
+    }
+
+    fn intern(&mut self, name: &str) -> Bag {
+        if let Some(&bag) = self.index.get(name) {
+            return bag;
+        }
+
+        let bag = self.names.len();
+        self.names.push(name.to_string());
+        self.index.insert(name.to_string(), bag);
+        self.contents.push(Vec::new());
+        self.containers.push(Vec::new());
+        bag
+    }
+
+    /// Kahn's algorithm, outer bags first; what is left over is cyclic.
+    fn topological_order(&self) -> Result<Vec<Bag>, Cycle> {
+        let mut incoming: Vec<usize> = self.containers.iter().map(Vec::len).collect();
+        let mut ready: VecDeque<Bag> = (0..self.len()).filter(|&bag| incoming[bag] == 0).collect();
+        let mut order = Vec::with_capacity(self.len());
+
+        while let Some(bag) = ready.pop_front() {
+            order.push(bag);
+
+            for &(_, inner) in self.contents[bag].iter() {
+                incoming[inner] -= 1;
+
+                if incoming[inner] == 0 {
+                    ready.push_back(inner);
+                }
+            }
+        }
+
+        match (0..self.len()).find(|&bag| incoming[bag] > 0) {
+            Some(start) => Err(self.cycle_from(start, &incoming)),
+            None => Ok(order)
+        }
+    }
+
+    /// Walks from a left over bag to one of its left over containers until a
+    /// bag comes round again, then starts the cycle at its first mentioned bag.
+    fn cycle_from(&self, start: Bag, incoming: &[usize]) -> Cycle {
+        let mut walked = vec![start];
+
+        loop {
+            let &bag = walked.last().expect("started");
+            let (_, outer) = self.containers[bag].iter().find(|&&(_, outer)| incoming[outer] > 0).expect("a left over bag has a left over container");
+
+            if let Some(pos) = walked.iter().position(|&b| b == *outer) {
+                let mut cycle: Vec<Bag> = walked[pos..].iter().rev().copied().collect();
+                let first = (0..cycle.len()).min_by_key(|&i| cycle[i]).expect("not empty");
+                cycle.rotate_left(first);
+
+                return Cycle { bags: cycle.into_iter().map(|b| self.names[b].clone()).collect() };
+            }
+
+            walked.push(*outer);
+        }
+    }
+
+    pub fn len(&self) -> usize {
+        self.names.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.names.is_empty()
+    }
+
+    pub fn bag(&self, name: &str) -> Option<Bag> {
+        self.index.get(name).copied()
+    }
+
+    pub fn name(&self, bag: Bag) -> &str {
+        &self.names[bag]
+    }
+
+    /// Counts and bags directly inside `bag`.
+    pub fn contents(&self, bag: Bag) -> &[(usize, Bag)] {
+        &self.contents[bag]
+    }
+
+    /// Bags directly containing `bag`, with how many of it each holds.
+    pub fn containers(&self, bag: Bag) -> &[(usize, Bag)] {
+        &self.containers[bag]
+    }
+
+    /// Every bag, each before the bags inside it.
+    pub fn topological(&self) -> &[Bag] {
+        &self.order
+    }
+
+    /// Every bag that eventually contains `bag`, in topological order.
+    pub fn outer(&self, bag: Bag) -> Vec<Bag> {
+        let mut seen = vec![false; self.len()];
+        let mut queue = VecDeque::from(vec![bag]);
+
+        while let Some(inner) = queue.pop_front() {
+            for &(_, outer) in self.containers[inner].iter() {
+                if !seen[outer] {
+                    seen[outer] = true;
+                    queue.push_back(outer);
+                }
+            }
+        }
+
+        self.order.iter().copied().filter(|&b| seen[b]).collect()
+    }
+
+    /// Number of bags making up `bag`, itself included.
+    pub fn total(&self, bag: Bag) -> usize {
+        self.totals[bag]
+    }
+
+    /// Every way `inner` ends up inside `outer`, by bags in topological order.
+    pub fn chains(&self, outer: Bag, inner: Bag) -> Vec<Chain> {
+        let mut found = Vec::new();
+        self.extend_chains(&mut vec![outer], 1, inner, &mut found);
+        found
+    }
+
+    fn extend_chains(&self, path: &mut Vec<Bag>, count: usize, inner: Bag, found: &mut Vec<Chain>) {
+        let &bag = path.last().expect("starts at the outer bag");
+
+        if bag == inner && path.len() > 1 {
+            found.push(Chain { bags: path.clone(), count });
+            return;
+        }
+
+        for &(n, next) in self.contents[bag].iter() {
+            path.push(next);
+            self.extend_chains(path, count * n, inner, found);
+            path.pop();
+        }
+    }
+
+    /// A chain as colours with the count of each bag inside the one before.
+    pub fn explain(&self, chain: &Chain) -> String {
+        let mut text = self.names[chain.bags[0]].clone();
+
+        for pair in chain.bags.windows(2) {
+            let (n, _) = self.contents[pair[0]].iter().find(|&&(_, b)| b == pair[1]).expect("an edge");
+            text += &format!(" -{}-> {}", n, self.names[pair[1]]);
+        }
+
+        text + &format!(" = {}", chain.count)
+    }
+
+    /// Graphviz source, an edge per rule labelled and weighted by the count.
+    pub fn to_dot(&self) -> String {
+        let mut dot = "digraph bags {\n".to_string();
+
+        for bag in 0..self.len() {
+            dot += &format!("    {};\n", dot_id(&self.names[bag]));
+        }
+
+        for bag in 0..self.len() {
+            for &(n, inner) in self.contents[bag].iter() {
+                dot += &format!("    {} -> {} [label={}, weight={}];\n", dot_id(&self.names[bag]), dot_id(&self.names[inner]), n, n);
+            }
+        }
+
+        dot + "}\n"
+    }
+
+    /// A JSON object from each colour to the counts and colours it contains.
+    pub fn to_json(&self) -> String {
+        let bags: Vec<String> = (0..self.len())
+            .map(|bag| {
+                let contents: Vec<String> = self.contents[bag].iter()
+                    .map(|&(n, inner)| format!(r#"{{"count": {}, "bag": {}}}"#, n, json_string(&self.names[inner])))
+                    .collect();
+
+                format!("{}: [{}]", json_string(&self.names[bag]), contents.join(", "))
+            })
+            .collect();
+
+        format!("{{{}}}", bags.join(",\n "))
+    }
+}
+
+/// A way a bag ends up inside another, the bags from the outside in and how
+/// many of the innermost one it makes for.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Chain {
+    pub bags: Vec<Bag>,
+    pub count: usize
+}
+
+/// Every chain behind `part` for a shiny gold bag: the ones it ends up in
+/// for part one, the ones inside it for part two. Dot and JSON give the
+/// whole graph, JSON with the chains.
+pub fn report(part: Part, input: &str, format: Format) -> Result<String, ParseError> {
+    let graph = solver::parse::<Day7>(input)?;
+
+    let chains: Vec<Chain> = match graph.bag("shiny gold") {
+        Some(gold) if part == Part::One => graph.outer(gold).into_iter().flat_map(|outer| graph.chains(outer, gold)).collect(),
+        Some(gold) => graph.topological().iter().flat_map(|&inner| graph.chains(gold, inner)).collect(),
+        None => Vec::new()
+    };
+
+    Ok(match format {
+        Format::Dot => graph.to_dot(),
+        Format::Json => {
+            let chains: Vec<String> = chains.iter()
+                .map(|c| {
+                    let bags: Vec<String> = c.bags.iter().map(|&b| json_string(graph.name(b))).collect();
+                    format!(r#"{{"bags": [{}], "count": {}}}"#, bags.join(", "), c.count)
+                })
+                .collect();
+
+            format!("{{\"graph\": {},\n \"chains\": [{}]}}\n", graph.to_json(), chains.join(",\n  "))
+        },
+        _ => chains.iter().map(|c| graph.explain(c) + "\n").collect()
+    })
+}
+
+pub struct Day7;
+
+impl Solver for Day7 {
+    const DAY: usize = 7;
+
+    type Input = Graph;
+    type One = usize;
+    type Two = usize;
+
+    /// A cycle is reported at the rule of its first bag, too many bags at
+    /// the rule of the bag holding them.
+    fn parse(input: &str) -> Result<Graph, ParseError> {
+        Graph::new(&rules(input)?).map_err(|err| {
+            let (bag, expected) = match &err {
+                GraphError::Cycle(cycle) => (&cycle.bags[0], format!("rules without a cycle, not {}", cycle)),
+                GraphError::Overflow { bag } => (bag, format!("rules with fewer bags, not {}", err))
+            };
+
+            let line = input.lines().find(|line| line.strip_prefix(bag.as_str()).is_some_and(|rest| rest.starts_with(CONTAIN))).unwrap_or(input);
+            ParseError::new(&line[..bag.len().min(line.len())], expected)
+        })
+    }
+
+    fn part_one(graph: &Graph) -> usize {
+        graph.bag("shiny gold").map_or(0, |bag| graph.outer(bag).len())
+    }
+
+    fn part_two(graph: &Graph) -> usize {
+        graph.total(graph.bag("shiny gold").expect("bag exists")) - 1
+    }
+}
+
+/// Examples from the puzzle text.
+pub const EXAMPLE_1: &str = r"light red bags contain 1 bright white bag, 2 muted yellow bags.
+dark orange bags contain 3 bright white bags, 4 muted yellow bags.
+bright white bags contain 1 shiny gold bag.
+muted yellow bags contain 2 shiny gold bags, 9 faded blue bags.
+shiny gold bags contain 1 dark olive bag, 2 vibrant plum bags.
+dark olive bags contain 3 faded blue bags, 4 dotted black bags.
+vibrant plum bags contain 5 faded blue bags, 6 dotted black bags.
+faded blue bags contain no other bags.
+dotted black bags contain no other bags.";
+pub const EXAMPLE_2: &str = r"shiny gold bags contain 2 dark red bags.
+dark red bags contain 2 dark orange bags.
+dark orange bags contain 2 dark yellow bags.
+dark yellow bags contain 2 dark green bags.
+dark green bags contain 2 dark blue bags.
+dark blue bags contain 2 dark violet bags.
+dark violet bags contain no other bags.";
+
+#[test]
+fn example_1() {
+    let rules = Day7::parse(EXAMPLE_1).expect("correct parse");
+    assert_eq!(Day7::part_one(&rules), 4);
+    assert_eq!(Day7::part_two(&rules), 32);
+}
+
+#[test]
+fn example_2() {
+    let rules = Day7::parse(EXAMPLE_2).expect("correct parse");
+    assert_eq!(Day7::part_two(&rules), 126);
+}
+
+#[test]
+fn graph() {
+    let graph = Day7::parse(EXAMPLE_1).unwrap();
+    let gold = graph.bag("shiny gold").expect("listed");
+    let names = |bags: &[Bag]| bags.iter().map(|&b| graph.name(b)).collect::<Vec<_>>();
+
+    assert_eq!(graph.len(), 9);
+    assert_eq!(names(&graph.outer(gold)), ["light red", "dark orange", "bright white", "muted yellow"]);
+    assert_eq!(graph.containers(gold).iter().map(|&(n, b)| (n, graph.name(b))).collect::<Vec<_>>(), [(1, "bright white"), (2, "muted yellow")]);
+    assert_eq!(graph.total(graph.bag("dark olive").unwrap()), 8);
+
+    let position = |bag: Bag| graph.topological().iter().position(|&b| b == bag).unwrap();
+    for bag in 0..graph.len() {
+        assert!(graph.contents(bag).iter().all(|&(_, inner)| position(bag) < position(inner)));
+    }
+
+    let cyclic = "a bags contain 1 b bag.\nb bags contain 2 c bags.\nc bags contain 1 a bag, 1 d bag.\nd bags contain no other bags.";
+    let cycle = Graph::new(&rules(cyclic).unwrap()).unwrap_err();
+    assert_eq!(cycle.to_string(), "a -> b -> c -> a");
+
+    let err = crate::solver::parse::<Day7>(cyclic).unwrap_err();
+    assert_eq!((err.line, err.column, err.text.as_str()), (1, 1, "a"));
+    assert_eq!(Graph::new(&rules("x bags contain 1 x bag.").unwrap()).unwrap_err(), GraphError::Cycle(Cycle { bags: vec!["x".to_string()] }));
+
+    let deep = (0..5).map(|i| format!("b{} bags contain 100000 b{} bags.\n", i, i + 1)).collect::<String>();
+    assert_eq!(Graph::new(&rules(&deep).unwrap()).unwrap_err(), GraphError::Overflow { bag: "b1".to_string() });
+
+    let err = crate::solver::parse::<Day7>(&deep).unwrap_err();
+    assert_eq!((err.line, err.text.as_str()), (2, "b1"));
+}
+
+#[test]
+fn export() {
+    let graph = Day7::parse(EXAMPLE_1).unwrap();
+    let bag = |name| graph.bag(name).unwrap();
+    let gold = bag("shiny gold");
+
+    let explained: Vec<String> = graph.chains(bag("light red"), gold).iter().map(|c| graph.explain(c)).collect();
+    assert_eq!(explained, ["light red -1-> bright white -1-> shiny gold = 1", "light red -2-> muted yellow -2-> shiny gold = 4"]);
+
+    let inside: usize = (0..graph.len()).flat_map(|b| graph.chains(gold, b)).map(|c| c.count).sum();
+    assert_eq!(inside, Day7::part_two(&graph));
+    assert_eq!(report(Part::Two, EXAMPLE_1, Format::Text).unwrap().lines().count(), 6);
+
+    let dot = graph.to_dot();
+    assert!(dot.starts_with("digraph bags {\n    \"light red\";\n"));
+    assert!(dot.contains("    \"muted yellow\" -> \"faded blue\" [label=9, weight=9];\n"));
+
+    let json = Day7::parse(EXAMPLE_2).unwrap().to_json();
+    assert!(json.starts_with(r#"{"shiny gold": [{"count": 2, "bag": "dark red"}],"#));
+    assert!(json.ends_with(r#""dark violet": []}"#));
+}
@@ -0,0 +1,80 @@
+//! Day 8: Handheld Halting.
+
+use crate::error::ParseError;
+use crate::solver::Solver;
+use crate::vm::{self, InstructionSet, Limits, Machine, Program, Run};
+
+lazy_static! {
+    static ref HANDHELD: InstructionSet = InstructionSet::handheld();
+}
+
+/// Runs `program` until an instruction is about to execute a second time or
+/// the instruction pointer leaves the program.
+pub fn run(program: &Program) -> Run {
+    vm::run(program, Machine::new(), Limits::default())
+}
+
+fn accum_before_repeat(program: &Program) -> Option<isize> {
+    Some(run(program)).filter(Run::looped).map(|run| run.machine.register('a'))
+}
+
+/// Accumulator after the program terminates with exactly one `nop`/`jmp` flipped.
+pub fn single_insn_patch(program: &Program) -> Option<isize> {
+    let (nop, jmp) = (HANDHELD.get("nop").expect("handheld"), HANDHELD.get("jmp").expect("handheld"));
+    let mut program = program.clone();
+
+    for i in 0..program.len() {
+        let op = program[i].op;
+        let flipped = if op == nop { jmp } else if op == jmp { nop } else { continue };
+        program[i].op = flipped;
+
+        let run = run(&program);
+        if run.halted() {
+            return Some(run.machine.register('a'));
+        }
+
+        program[i].op = op;
+    }
+
+    None
+}
+
+pub struct Day8;
+
+impl Solver for Day8 {
+    const DAY: usize = 8;
+
+    type Input = Program;
+    type One = isize;
+    type Two = isize;
+
+    fn parse(input: &str) -> Result<Program, ParseError> {
+        HANDHELD.parse(input)
+    }
+
+    fn part_one(program: &Program) -> isize {
+        accum_before_repeat(program).expect("exists")
+    }
+
+    fn part_two(program: &Program) -> isize {
+        single_insn_patch(program).expect("exists")
+    }
+}
+
+/// Example from the puzzle text.
+pub const EXAMPLE: &str = r"nop +0
+acc +1
+jmp +4
+acc +3
+jmp -3
+acc -99
+acc +1
+jmp -4
+acc +6";
+
+#[test]
+fn example() {
+    let program = Day8::parse(EXAMPLE).expect("correct parse");
+    assert_eq!(accum_before_repeat(&program), Some(5));
+    assert_eq!(single_insn_patch(&program), Some(8));
+}
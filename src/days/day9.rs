@@ -0,0 +1,140 @@
+//! Day 9: Encoding Error.
+
+use std::collections::VecDeque;
+use std::io::BufRead;
+
+use crate::Part;
+use crate::error::{ParseError, parse_as};
+use crate::solver::{Answer, Solver};
+use crate::stream::{self, StreamError};
+
+const PREAMBLE: usize = 25;
+
+fn number(line: &str) -> Result<usize, ParseError> {
+    parse_as(line, "a number")
+}
+
+fn numbers(input: &str) -> Result<Vec<usize>, ParseError> {
+    input.lines().map(|l| number(l.trim())).collect()
+}
+
+/// First number that is not a sum of two distinct numbers among the `preamble` before it.
+pub fn first_incorrect_slice(numbers: &[usize], preamble: usize) -> Option<usize> {
+    for k in preamble..numbers.len() {
+        let window = &numbers[k-preamble..k];
+        let sum = numbers[k];
+        let correct = window.iter().any(|&i| window.iter().any(|&j| i != j && i + j == sum));
+        if !correct {
+            return Some(sum)
+        }
+    }
+
+    None
+}
+
+/// [`first_incorrect_slice`] over numbers read one per line, keeping only
+/// the last `preamble` of them.
+pub fn first_incorrect_stream(reader: impl BufRead, preamble: usize) -> Result<Option<usize>, StreamError> {
+    let mut window = VecDeque::with_capacity(preamble + 1);
+
+    for sum in stream::parse_lines(Day9::DAY, reader, number) {
+        let sum = sum?;
+
+        if window.len() == preamble {
+            let correct = window.iter().any(|&i| window.iter().any(|&j| i != j && i + j == sum));
+            if !correct {
+                return Ok(Some(sum));
+            }
+
+            window.pop_front();
+        }
+
+        window.push_back(sum);
+    }
+
+    Ok(None)
+}
+
+/// Solves `part` reading the input from `reader` in bounded memory. Part two
+/// looks for a range anywhere before the incorrect number, so it cannot be
+/// streamed.
+pub fn stream(part: Part, reader: &mut dyn BufRead) -> Result<Option<Answer>, StreamError> {
+    match part {
+        Part::One => Ok(Some(first_incorrect_stream(reader, PREAMBLE)?.expect("exists").into())),
+        Part::Two => Ok(None)
+    }
+}
+
+/// Sum of the smallest and largest number in a contiguous range adding up to `sum`.
+pub fn weakness(numbers: &[usize], sum: usize) -> Option<usize> {
+    let mut sums = (0..numbers.len())
+        .flat_map(|start| {
+            numbers[start..].iter()
+                .enumerate()
+                .scan(0usize, move |state, (i, &x)| {
+                    *state += x;
+                    Some((*state, start, i+1))
+                })
+        });
+
+    let (_, start, len) = sums.find(|&(contiguous_sum, _, _)| contiguous_sum == sum)?;
+    let res = &numbers[start..(start+len)];
+    Some(res.iter().min()? + res.iter().max()?)
+}
+
+fn find_weakness(numbers: &[usize], preamble: usize) -> Option<usize> {
+    let invalid_number = first_incorrect_slice(numbers, preamble)?;
+    weakness(numbers, invalid_number)
+}
+
+pub struct Day9;
+
+impl Solver for Day9 {
+    const DAY: usize = 9;
+
+    type Input = Vec<usize>;
+    type One = usize;
+    type Two = usize;
+
+    fn parse(input: &str) -> Result<Vec<usize>, ParseError> {
+        numbers(input)
+    }
+
+    fn part_one(numbers: &Vec<usize>) -> usize {
+        first_incorrect_slice(numbers, PREAMBLE).expect("exists")
+    }
+
+    fn part_two(numbers: &Vec<usize>) -> usize {
+        find_weakness(numbers, PREAMBLE).expect("exists")
+    }
+}
+
+/// Example from the puzzle text, with a preamble of 5 numbers.
+pub const EXAMPLE: &str = r"35
+20
+15
+25
+47
+40
+62
+55
+65
+95
+102
+117
+150
+182
+127
+219
+299
+277
+309
+576";
+
+#[test]
+fn example() {
+    let numbers = Day9::parse(EXAMPLE).expect("correct parse");
+    assert_eq!(first_incorrect_slice(&numbers, 5), Some(127));
+    assert_eq!(first_incorrect_stream(EXAMPLE.as_bytes(), 5).unwrap(), Some(127));
+    assert_eq!(find_weakness(&numbers, 5), Some(62));
+}
@@ -0,0 +1,147 @@
+use std::io::BufRead;
+
+use crate::Part;
+use crate::error::ParseError;
+use crate::input::Source;
+use crate::report::Format;
+use crate::schema::{Schema, SchemaError};
+use crate::solver::{self, Answer};
+use crate::stream::StreamError;
+
+pub mod day1;
+pub mod day2;
+pub mod day3;
+pub mod day4;
+pub mod day5;
+pub mod day6;
+pub mod day7;
+pub mod day8;
+pub mod day9;
+pub mod day10;
+pub mod day11;
+pub mod day12;
+pub mod day13;
+pub mod day14;
+pub mod day15;
+pub mod day16;
+pub mod day17;
+pub mod day18;
+pub mod day19;
+pub mod day20;
+pub mod day21;
+pub mod day22;
+pub mod day23;
+pub mod day24;
+pub mod day25;
+
+pub const DAYS: usize = 25;
+
+type Run = fn(Part, &str) -> Result<Option<Answer>, ParseError>;
+
+const SOLVERS: [Run; DAYS] = [
+    solver::run::<day1::Day1>,
+    solver::run::<day2::Day2>,
+    solver::run::<day3::Day3>,
+    solver::run::<day4::Day4>,
+    solver::run::<day5::Day5>,
+    solver::run::<day6::Day6>,
+    solver::run::<day7::Day7>,
+    solver::run::<day8::Day8>,
+    solver::run::<day9::Day9>,
+    solver::run::<day10::Day10>,
+    solver::run::<day11::Day11>,
+    solver::run::<day12::Day12>,
+    solver::run::<day13::Day13>,
+    solver::run::<day14::Day14>,
+    solver::run::<day15::Day15>,
+    solver::run::<day16::Day16>,
+    solver::run::<day17::Day17>,
+    solver::run::<day18::Day18>,
+    solver::run::<day19::Day19>,
+    solver::run::<day20::Day20>,
+    solver::run::<day21::Day21>,
+    solver::run::<day22::Day22>,
+    solver::run::<day23::Day23>,
+    solver::run::<day24::Day24>,
+    solver::run::<day25::Day25>,
+];
+
+/// What a day may use besides its input. Only day 4 has anything to set,
+/// the fields it checks passports against, the bundled ones if `None`.
+#[derive(Clone, Debug, Default)]
+pub struct Options {
+    pub passport: Option<Schema>
+}
+
+impl Options {
+    /// Options for the inputs of `source`, from the files next to them.
+    pub fn load(source: &Source) -> Result<Options, SchemaError> {
+        Ok(Options { passport: day4::find_schema(source)? })
+    }
+}
+
+/// Solves one part of a day, `Ok(None)` if there is no such day or part.
+pub fn solve(day: usize, part: Part, input: &str) -> Result<Option<Answer>, ParseError> {
+    solve_with(day, part, input, &Options::default())
+}
+
+/// Like [`solve`], with `options` for the days that use them.
+pub fn solve_with(day: usize, part: Part, input: &str, options: &Options) -> Result<Option<Answer>, ParseError> {
+    if let (4, Some(schema)) = (day, &options.passport) {
+        return day4::solve_with(schema, part, input);
+    }
+
+    match day.checked_sub(1).and_then(|idx| SOLVERS.get(idx)) {
+        Some(run) => run(part, input),
+        None => Ok(None)
+    }
+}
+
+type Stream = fn(Part, &mut dyn BufRead) -> Result<Option<Answer>, StreamError>;
+
+const BOTH: &[Part] = &[Part::One, Part::Two];
+
+/// Days that can be solved reading their input a line at a time, and the
+/// parts that can.
+const STREAMS: [(usize, &[Part], Stream); 5] = [
+    (1, &[Part::One], day1::stream),
+    (2, BOTH, day2::stream),
+    (5, BOTH, day5::stream),
+    (9, &[Part::One], day9::stream),
+    (10, BOTH, day10::stream)
+];
+
+/// Parts of `day` that [`solve_stream`] can solve, none if it cannot
+/// stream that day at all.
+pub fn streamed_parts(day: usize) -> &'static [Part] {
+    STREAMS.iter().find(|&&(d, _, _)| d == day).map_or(&[], |&(_, parts, _)| parts)
+}
+
+/// Solves one part of a day reading its input from `reader` in bounded
+/// memory, `Ok(None)` if that day or part cannot be solved that way.
+pub fn solve_stream(day: usize, part: Part, reader: &mut dyn BufRead) -> Result<Option<Answer>, StreamError> {
+    match STREAMS.iter().find(|&&(d, _, _)| d == day) {
+        Some((_, _, stream)) => stream(part, reader),
+        None => Ok(None)
+    }
+}
+
+type Report = fn(Part, &str, Format) -> Result<String, ParseError>;
+
+/// Days that can show how they arrived at an answer.
+const REPORTS: [(usize, Report); 4] = [
+    (2, day2::report),
+    (4, day4::report),
+    (5, day5::report),
+    (7, day7::report)
+];
+
+/// Details on one part of a day in `format`, `Ok(None)` if that day has
+/// nothing more to say than its answer. Days print text for formats they
+/// have no use for.
+pub fn report(day: usize, part: Part, input: &str, format: Format) -> Result<Option<String>, ParseError> {
+    match REPORTS.iter().find(|&&(d, _)| d == day) {
+        Some((_, report)) => report(part, input, format).map(Some),
+        None => Ok(None)
+    }
+}
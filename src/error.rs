@@ -0,0 +1,128 @@
+use std::fmt;
+use std::str::FromStr;
+
+/// Malformed puzzle input: what was found where, and what should have been there.
+///
+/// Parsers only know the offending text, which they slice out of the input;
+/// line and column are worked out afterwards by [`ParseError::locate`], which
+/// `solver::parse` does for every day.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct ParseError {
+    /// Day of the puzzle, 0 until located.
+    pub day: usize,
+    /// 1-based line of the offending text, 0 until located.
+    pub line: usize,
+    /// 1-based column in characters, 0 until located.
+    pub column: usize,
+    pub text: String,
+    pub expected: String,
+    addr: usize
+}
+
+impl ParseError {
+    /// `text`, a slice of the puzzle input, is not `expected`.
+    pub fn new(text: &str, expected: impl Into<String>) -> Self {
+        ParseError {
+            day: 0,
+            line: 0,
+            column: 0,
+            text: text.to_string(),
+            expected: expected.into(),
+            addr: text.as_ptr() as usize
+        }
+    }
+
+    /// The input ended while still expecting something.
+    pub fn end(input: &str, expected: impl Into<String>) -> Self {
+        ParseError::new(&input[input.len()..], expected)
+    }
+
+    /// Fills in day, line and column, given the input the text was sliced from.
+    ///
+    /// Text that does not point into `input` is left without a position.
+    pub fn locate(mut self, day: usize, input: &str) -> Self {
+        self.day = day;
+
+        let start = input.as_ptr() as usize;
+        if let Some(offset) = self.addr.checked_sub(start).filter(|&o| o <= input.len()) {
+            if let Some(before) = input.get(..offset) {
+                let line_start = before.rfind('\n').map_or(0, |pos| pos + 1);
+                self.line = before.matches('\n').count() + 1;
+                self.column = before[line_start..].chars().count() + 1;
+            }
+        }
+
+        self
+    }
+
+    /// Like [`ParseError::locate`] for text sliced from `line`, line `number`
+    /// of an input read one line at a time.
+    pub fn locate_line(self, day: usize, number: usize, line: &str) -> Self {
+        let mut err = self.locate(day, line);
+
+        if err.line != 0 {
+            err.line += number - 1;
+        }
+
+        err
+    }
+
+    /// The offending line of `input`, with the text underlined.
+    pub fn excerpt(&self, input: &str) -> Option<String> {
+        let line = input.lines().nth(self.line.checked_sub(1)?)?;
+        let indent = self.column.checked_sub(1)?;
+        let marks = self.text.chars().count().min(line.chars().count().saturating_sub(indent)).max(1);
+        let gutter = " ".repeat(self.line.to_string().len());
+
+        Some(format!("{} |\n{} | {}\n{} | {}{}", gutter, self.line, line, gutter, " ".repeat(indent), "^".repeat(marks)))
+    }
+}
+
+impl fmt::Display for ParseError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if self.day != 0 {
+            write!(f, "day {}, ", self.day)?;
+        }
+
+        if self.line != 0 {
+            write!(f, "line {}, column {}: ", self.line, self.column)?;
+        }
+
+        if self.text.is_empty() {
+            write!(f, "expected {}, found nothing", self.expected)
+        } else {
+            write!(f, "expected {}, found {:?}", self.expected, self.text)
+        }
+    }
+}
+
+impl std::error::Error for ParseError {}
+
+/// Parses `text` with [`FromStr`], reporting it as not `expected` on failure.
+pub fn parse_as<T: FromStr>(text: &str, expected: &str) -> Result<T, ParseError> {
+    text.parse().map_err(|_| ParseError::new(text, expected))
+}
+
+/// Checks that `line` only has characters from `allowed`, reporting the
+/// first one that is not.
+pub fn only_chars(line: &str, allowed: &str, expected: &str) -> Result<(), ParseError> {
+    match line.char_indices().find(|&(_, c)| !allowed.contains(c)) {
+        Some((pos, c)) => Err(ParseError::new(&line[pos..pos + c.len_utf8()], expected)),
+        None => Ok(())
+    }
+}
+
+#[test]
+fn located() {
+    let input = "nop +0\nacc +1\njmp +x\n";
+    let line = input.lines().nth(2).unwrap();
+    let err = parse_as::<isize>(&line[4..], "an offset").unwrap_err().locate(8, input);
+
+    assert_eq!((err.day, err.line, err.column), (8, 3, 5));
+    assert_eq!(err.to_string(), r#"day 8, line 3, column 5: expected an offset, found "+x""#);
+
+    assert_eq!(err.excerpt(input).unwrap(), "  |\n3 | jmp +x\n  |     ^^");
+
+    let err = ParseError::end(input, "an instruction").locate(8, input);
+    assert_eq!((err.line, err.column), (4, 1));
+}
@@ -0,0 +1,366 @@
+use std::fmt;
+use std::ops::{Index, IndexMut};
+use std::slice;
+
+use crate::error::ParseError;
+
+/// Column and row of a cell, counted from the top left corner.
+pub type Pos = (usize, usize);
+
+const ORTHOGONAL: [(isize, isize); 4] = [(0, -1), (1, 0), (0, 1), (-1, 0)];
+
+const ALL_AROUND: [(isize, isize); 8] = [(-1, -1), (0, -1), (1, -1), (-1, 0), (1, 0), (-1, 1), (0, 1), (1, 1)];
+
+/// Neighbours on a hex map stored in axial coordinates.
+pub(crate) const HEX: [(isize, isize); 6] = [(1, 0), (-1, 0), (0, -1), (1, -1), (-1, 1), (0, 1)];
+
+/// How a cell is drawn when a grid is displayed.
+pub trait ToChar {
+    fn to_char(&self) -> char;
+}
+
+impl ToChar for bool {
+    fn to_char(&self) -> char {
+        if *self { '#' } else { '.' }
+    }
+}
+
+impl ToChar for char {
+    fn to_char(&self) -> char {
+        *self
+    }
+}
+
+/// Reads `#` as set and `.` as unset, the usual character map of booleans.
+pub fn pixel(c: char) -> Option<bool> {
+    match c {
+        '#' => Some(true),
+        '.' => Some(false),
+        _ => None
+    }
+}
+
+/// A rectangular map of cells, stored row by row.
+#[derive(Clone, Debug, Eq, Hash, PartialEq)]
+pub struct Grid<T> {
+    w: usize,
+    h: usize,
+    cells: Vec<T>
+}
+
+impl<T> Grid<T> {
+    /// A `w` by `h` grid from its cells row by row, `None` unless there are `w * h` of them.
+    pub fn from_vec(w: usize, h: usize, cells: Vec<T>) -> Option<Self> {
+        if cells.len() == w * h {
+            Some(Grid { w, h, cells })
+        } else {
+            None
+        }
+    }
+
+    /// Builds a grid from rows of equal length.
+    pub fn from_rows(rows: Vec<Vec<T>>) -> Option<Self> {
+        let w = rows.first().map_or(0, Vec::len);
+
+        if rows.iter().any(|row| row.len() != w) {
+            return None;
+        }
+
+        let h = rows.len();
+        Grid::from_vec(w, h, rows.into_iter().flatten().collect())
+    }
+
+    pub fn from_fn(w: usize, h: usize, mut cell: impl FnMut(Pos) -> T) -> Self {
+        let cells = (0..h).flat_map(|y| (0..w).map(move |x| (x, y))).map(&mut cell).collect();
+        Grid { w, h, cells }
+    }
+
+    /// Parses one row per line, `cell` turning each character into a cell or
+    /// rejecting it as not `expected`.
+    pub fn from_lines<'a>(
+        lines: impl IntoIterator<Item=&'a str>,
+        expected: &str,
+        mut cell: impl FnMut(char) -> Option<T>) -> Result<Self, ParseError>
+    {
+        let mut rows: Vec<Vec<T>> = Vec::new();
+
+        for line in lines {
+            let row = line.char_indices()
+                .map(|(pos, c)| cell(c).ok_or_else(|| ParseError::new(&line[pos..pos + c.len_utf8()], expected)))
+                .collect::<Result<Vec<_>, _>>()?;
+
+            if let Some(first) = rows.first().filter(|first| first.len() != row.len()) {
+                return Err(ParseError::new(line, format!("a row of {} cells", first.len())));
+            }
+
+            rows.push(row);
+        }
+
+        Ok(Grid::from_rows(rows).expect("rows of equal length"))
+    }
+
+    /// Parses a character map, one trimmed line per row.
+    pub fn parse(input: &str, expected: &str, cell: impl FnMut(char) -> Option<T>) -> Result<Self, ParseError> {
+        Grid::from_lines(input.lines().map(str::trim), expected, cell)
+    }
+
+    pub fn width(&self) -> usize {
+        self.w
+    }
+
+    pub fn height(&self) -> usize {
+        self.h
+    }
+
+    fn index_of(&self, (x, y): Pos) -> Option<usize> {
+        if x < self.w && y < self.h {
+            Some(y * self.w + x)
+        } else {
+            None
+        }
+    }
+
+    pub fn get(&self, pos: Pos) -> Option<&T> {
+        self.index_of(pos).map(|i| &self.cells[i])
+    }
+
+    pub fn get_mut(&mut self, pos: Pos) -> Option<&mut T> {
+        self.index_of(pos).map(move |i| &mut self.cells[i])
+    }
+
+    pub fn row(&self, y: usize) -> &[T] {
+        &self.cells[y * self.w..(y + 1) * self.w]
+    }
+
+    pub fn rows(&self) -> impl Iterator<Item=&[T]> {
+        (0..self.h).map(move |y| self.row(y))
+    }
+
+    /// All cells, row by row.
+    pub fn cells(&self) -> slice::Iter<'_, T> {
+        self.cells.iter()
+    }
+
+    /// All positions, row by row.
+    pub fn positions(&self) -> impl Iterator<Item=Pos> {
+        let w = self.w;
+        (0..self.h).flat_map(move |y| (0..w).map(move |x| (x, y)))
+    }
+
+    pub fn map<U>(&self, mut f: impl FnMut(Pos, &T) -> U) -> Grid<U> {
+        let cells = self.positions().zip(self.cells.iter()).map(|(pos, cell)| f(pos, cell)).collect();
+        Grid { w: self.w, h: self.h, cells }
+    }
+
+    /// `pos` moved by `(dx, dy)`, if that is still on the grid.
+    pub fn offset(&self, (x, y): Pos, (dx, dy): (isize, isize)) -> Option<Pos> {
+        let x = (x as isize).checked_add(dx).filter(|&x| x >= 0)? as usize;
+        let y = (y as isize).checked_add(dy).filter(|&y| y >= 0)? as usize;
+
+        self.index_of((x, y)).map(|_| (x, y))
+    }
+
+    fn around<'a>(&'a self, pos: Pos, offsets: &'a [(isize, isize)]) -> impl Iterator<Item=Pos> + 'a {
+        offsets.iter().filter_map(move |&d| self.offset(pos, d))
+    }
+
+    /// The up to four cells sharing a side with `pos`.
+    pub fn neighbours4(&self, pos: Pos) -> impl Iterator<Item=Pos> + '_ {
+        self.around(pos, &ORTHOGONAL)
+    }
+
+    /// The up to eight cells sharing a side or a corner with `pos`.
+    pub fn neighbours8(&self, pos: Pos) -> impl Iterator<Item=Pos> + '_ {
+        self.around(pos, &ALL_AROUND)
+    }
+
+    /// The up to six neighbours of `pos` on a hex map in axial coordinates,
+    /// where both `(1, -1)` and `(-1, 1)` are adjacent.
+    pub fn hex_neighbours(&self, pos: Pos) -> impl Iterator<Item=Pos> + '_ {
+        self.around(pos, &HEX)
+    }
+
+    /// The first cell in each of the eight directions from `pos` that is not
+    /// `transparent`.
+    pub fn line_of_sight<'a>(&'a self, pos: Pos, transparent: impl Fn(&T) -> bool + 'a) -> impl Iterator<Item=Pos> + 'a {
+        ALL_AROUND.iter().filter_map(move |&d| {
+            let mut pos = self.offset(pos, d)?;
+
+            while transparent(&self[pos]) {
+                pos = self.offset(pos, d)?;
+            }
+
+            Some(pos)
+        })
+    }
+
+    /// The `w` by `h` part of the grid whose top left corner is `(x, y)`,
+    /// `None` if it does not fit.
+    pub fn view(&self, (x, y): Pos, w: usize, h: usize) -> Option<View<'_, T>> {
+        if x + w <= self.w && y + h <= self.h {
+            Some(View { grid: self, x, y, w, h })
+        } else {
+            None
+        }
+    }
+}
+
+impl<T: Clone> Grid<T> {
+    pub fn filled(w: usize, h: usize, value: T) -> Self {
+        Grid { w, h, cells: vec![value; w * h] }
+    }
+
+    /// One of the eight symmetries of the square, `t` in `0..8`: bit 1
+    /// mirrors left to right, then bit 2 top to bottom, then bit 4 transposes.
+    pub fn transformed(&self, t: usize) -> Self {
+        assert!(t < 8, "no transform {}", t);
+
+        let (w, h) = if t & 4 != 0 { (self.h, self.w) } else { (self.w, self.h) };
+
+        Grid::from_fn(w, h, |(x, y)| {
+            let (mut x, mut y) = if t & 4 != 0 { (y, x) } else { (x, y) };
+
+            if t & 2 != 0 {
+                y = self.h - 1 - y;
+            }
+
+            if t & 1 != 0 {
+                x = self.w - 1 - x;
+            }
+
+            self[(x, y)].clone()
+        })
+    }
+
+    /// All eight transforms, in the order of [`Grid::transformed`].
+    pub fn transforms(&self) -> impl Iterator<Item=Self> + '_ {
+        (0..8).map(move |t| self.transformed(t))
+    }
+
+    /// A copy of the `w` by `h` part whose top left corner is `pos`.
+    pub fn crop(&self, pos: Pos, w: usize, h: usize) -> Option<Self> {
+        self.view(pos, w, h).map(|view| view.to_grid())
+    }
+}
+
+/// The transform undoing transform `t` of [`Grid::transformed`].
+pub fn inverse_transform(t: usize) -> usize {
+    if t & 4 != 0 {
+        4 | (t & 1) << 1 | (t & 2) >> 1
+    } else {
+        t
+    }
+}
+
+impl<T> Index<Pos> for Grid<T> {
+    type Output = T;
+
+    fn index(&self, pos: Pos) -> &T {
+        self.get(pos).unwrap_or_else(|| panic!("{:?} outside a {}x{} grid", pos, self.w, self.h))
+    }
+}
+
+impl<T> IndexMut<Pos> for Grid<T> {
+    fn index_mut(&mut self, pos: Pos) -> &mut T {
+        let (w, h) = (self.w, self.h);
+        self.get_mut(pos).unwrap_or_else(|| panic!("{:?} outside a {}x{} grid", pos, w, h))
+    }
+}
+
+impl<T: ToChar> fmt::Display for Grid<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for row in self.rows() {
+            let line: String = row.iter().map(ToChar::to_char).collect();
+            writeln!(f, "{}", line)?;
+        }
+
+        Ok(())
+    }
+}
+
+/// A rectangular part of a grid, positions relative to its own top left corner.
+#[derive(Debug)]
+pub struct View<'a, T> {
+    grid: &'a Grid<T>,
+    x: usize,
+    y: usize,
+    w: usize,
+    h: usize
+}
+
+impl<T> Clone for View<'_, T> {
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<T> Copy for View<'_, T> {}
+
+impl<'a, T> View<'a, T> {
+    pub fn width(&self) -> usize {
+        self.w
+    }
+
+    pub fn height(&self) -> usize {
+        self.h
+    }
+
+    pub fn get(&self, (x, y): Pos) -> Option<&'a T> {
+        if x < self.w && y < self.h {
+            self.grid.get((self.x + x, self.y + y))
+        } else {
+            None
+        }
+    }
+
+    pub fn rows(&self) -> impl Iterator<Item=&'a [T]> {
+        let View { grid, x, y, w, h } = *self;
+        (y..y + h).map(move |row| &grid.row(row)[x..x + w])
+    }
+
+    pub fn to_grid(&self) -> Grid<T> where T: Clone {
+        Grid { w: self.w, h: self.h, cells: self.rows().flatten().cloned().collect() }
+    }
+}
+
+impl<T> Index<Pos> for View<'_, T> {
+    type Output = T;
+
+    fn index(&self, pos: Pos) -> &T {
+        self.get(pos).unwrap_or_else(|| panic!("{:?} outside a {}x{} view", pos, self.w, self.h))
+    }
+}
+
+#[test]
+fn transforms() {
+    let grid = Grid::parse("#..\n##.", "`.` or `#`", pixel).expect("correct parse");
+    assert_eq!(grid.to_string(), "#..\n##.\n");
+    assert_eq!(grid.transformed(1).to_string(), "..#\n.##\n");
+    assert_eq!(grid.transformed(4).to_string(), "##\n.#\n..\n");
+
+    let distinct: std::collections::HashSet<_> = grid.transforms().collect();
+    assert_eq!(distinct.len(), 8);
+
+    for t in 0..8 {
+        assert_eq!(grid.transformed(t).transformed(inverse_transform(t)), grid);
+    }
+
+    assert_eq!(grid.crop((1, 0), 2, 2).expect("fits").to_string(), "..\n#.\n");
+    assert!(grid.view((2, 0), 2, 1).is_none());
+}
+
+#[test]
+fn neighbours() {
+    let grid = Grid::parse("L.L\n...\nL.#", "`L`, `.` or `#`", Some).expect("correct parse");
+
+    assert_eq!(grid.neighbours4((0, 0)).count(), 2);
+    assert_eq!(grid.neighbours8((1, 1)).count(), 8);
+    assert_eq!(grid.hex_neighbours((0, 0)).collect::<Vec<_>>(), vec![(1, 0), (0, 1)]);
+
+    let mut seen: Vec<_> = grid.line_of_sight((0, 0), |&c| c == '.').collect();
+    seen.sort_unstable();
+    assert_eq!(seen, vec![(0, 2), (2, 0), (2, 2)]);
+
+    let err = Grid::parse("ab\nc", "a letter", |c| Some(c).filter(|_| c != 'c')).unwrap_err();
+    assert_eq!(err.text, "c");
+}
@@ -0,0 +1,147 @@
+use std::env;
+use std::fmt;
+use std::fs;
+use std::io::{self, BufRead, BufReader, Read};
+use std::path::{Path, PathBuf};
+use std::slice;
+
+pub const INPUT_DIR_VAR: &str = "AOC_INPUT_DIR";
+
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum Source {
+    Default,
+    Dir(PathBuf),
+    Path(PathBuf),
+    Stdin
+}
+
+impl Source {
+    pub fn from_arg(arg: &str) -> Self {
+        if arg == "-" {
+            Source::Stdin
+        } else {
+            Source::Path(arg.into())
+        }
+    }
+}
+
+#[derive(Debug)]
+pub enum InputError {
+    NotFound { day: usize, tried: Vec<PathBuf> },
+    Io { path: PathBuf, err: io::Error },
+    Stdin(io::Error)
+}
+
+impl fmt::Display for InputError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            InputError::NotFound { day, tried } => {
+                write!(f, "no input for day {}, tried:", day)?;
+                for path in tried {
+                    write!(f, " {}", path.display())?;
+                }
+                write!(f, " (pass an input path or set {})", INPUT_DIR_VAR)
+            },
+
+            InputError::Io { path, err } => write!(f, "cannot read {}: {}", path.display(), err),
+            InputError::Stdin(err) => write!(f, "cannot read stdin: {}", err)
+        }
+    }
+}
+
+impl std::error::Error for InputError {}
+
+pub fn file_name(day: usize) -> String {
+    format!("{}.txt", day)
+}
+
+/// Directories searched for `<day>.txt` when no explicit input is given:
+/// `$AOC_INPUT_DIR` if set, otherwise `inputs` in the current directory
+/// and then the one shipped next to the crate manifest.
+pub fn search_dirs() -> Vec<PathBuf> {
+    if let Some(dir) = env::var_os(INPUT_DIR_VAR) {
+        return vec![dir.into()];
+    }
+
+    let mut dirs = vec![PathBuf::from("inputs")];
+    let bundled = Path::new(env!("CARGO_MANIFEST_DIR")).join("inputs");
+
+    if !dirs.contains(&bundled) {
+        dirs.push(bundled);
+    }
+
+    dirs
+}
+
+fn read_file(path: &Path) -> Result<String, InputError> {
+    fs::read_to_string(path).map_err(|err| InputError::Io { path: path.to_path_buf(), err })
+}
+
+fn find_in(day: usize, dirs: &[PathBuf]) -> Result<PathBuf, InputError> {
+    let tried: Vec<PathBuf> = dirs.iter().map(|dir| dir.join(file_name(day))).collect();
+
+    match tried.iter().position(|path| path.is_file()) {
+        Some(idx) => Ok(tried[idx].clone()),
+        None => Err(InputError::NotFound { day, tried })
+    }
+}
+
+pub fn load(day: usize, source: &Source) -> Result<String, InputError> {
+    match source {
+        Source::Default => read_file(&find_in(day, &search_dirs())?),
+        Source::Dir(dir) => read_file(&find_in(day, slice::from_ref(dir))?),
+        Source::Path(path) => read_file(path),
+        Source::Stdin => {
+            let mut input = String::new();
+            io::stdin().read_to_string(&mut input).map_err(InputError::Stdin)?;
+            Ok(input)
+        }
+    }
+}
+
+fn open_file(path: &Path) -> Result<Box<dyn BufRead>, InputError> {
+    let file = fs::File::open(path).map_err(|err| InputError::Io { path: path.to_path_buf(), err })?;
+    Ok(Box::new(BufReader::new(file)))
+}
+
+/// Like [`load`], but only opens the input for reading it a line at a time.
+pub fn open(day: usize, source: &Source) -> Result<Box<dyn BufRead>, InputError> {
+    match source {
+        Source::Default => open_file(&find_in(day, &search_dirs())?),
+        Source::Dir(dir) => open_file(&find_in(day, slice::from_ref(dir))?),
+        Source::Path(path) => open_file(path),
+        Source::Stdin => Ok(Box::new(io::stdin().lock()))
+    }
+}
+
+/// 64-bit FNV-1a hash of `input`, telling inputs apart in reports.
+pub fn hash(input: &str) -> u64 {
+    input.bytes().fold(0xcbf29ce484222325, |hash, b| (hash ^ b as u64).wrapping_mul(0x100000001b3))
+}
+
+#[test]
+fn missing() {
+    let dir = env::temp_dir().join("aoc2020-missing-inputs");
+    let err = load(7, &Source::Dir(dir.clone())).unwrap_err();
+
+    match err {
+        InputError::NotFound { day, tried } => {
+            assert_eq!(day, 7);
+            assert_eq!(tried, vec![dir.join("7.txt")]);
+        },
+
+        _ => panic!("unexpected error: {}", err)
+    }
+}
+
+#[test]
+fn bundled() {
+    let input = load(15, &Source::Dir(Path::new(env!("CARGO_MANIFEST_DIR")).join("inputs"))).unwrap();
+    assert_eq!(input.trim(), "11,18,0,20,1,7,16");
+}
+
+#[test]
+fn fnv() {
+    assert_eq!(hash(""), 0xcbf29ce484222325);
+    assert_eq!(hash("a"), 0xaf63dc4c8601ec8c);
+}
@@ -0,0 +1,30 @@
+#[macro_use]
+extern crate lazy_static;
+
+pub mod answers;
+pub mod automaton;
+pub mod batch;
+pub mod days;
+pub mod error;
+pub mod grid;
+pub mod input;
+pub mod report;
+pub mod schema;
+pub mod solver;
+pub mod stream;
+pub mod vm;
+
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum Part {
+    One,
+    Two
+}
+
+impl Part {
+    pub fn number(self) -> usize {
+        match self {
+            Part::One => 1,
+            Part::Two => 2
+        }
+    }
+}
@@ -0,0 +1,176 @@
+use std::fmt::Write as _;
+use std::io::{self, Write};
+use std::str::FromStr;
+use std::time::Duration;
+
+use crate::Part;
+use crate::batch::{Done, Outcome};
+use crate::solver::Answer;
+
+/// How the runner prints its results.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum Format {
+    Text,
+    Json,
+    Tsv,
+    /// Graphviz, for `report` on days with a graph.
+    Dot
+}
+
+impl FromStr for Format {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "text" => Ok(Format::Text),
+            "json" => Ok(Format::Json),
+            "tsv" => Ok(Format::Tsv),
+            "dot" => Ok(Format::Dot),
+            _ => Err(format!("unknown format: {}", s))
+        }
+    }
+}
+
+/// One solved part: its answer, how long parsing and solving took, and which input it was for.
+#[derive(Clone, Debug)]
+pub struct Record {
+    pub day: usize,
+    pub part: Part,
+    pub answer: Answer,
+    pub elapsed: Duration,
+    pub input_hash: u64
+}
+
+pub(crate) fn json_string(s: &str) -> String {
+    let mut out = String::with_capacity(s.len() + 2);
+    out.push('"');
+
+    for c in s.chars() {
+        match c {
+            '"' => out.push_str("\\\""),
+            '\\' => out.push_str("\\\\"),
+            '\n' => out.push_str("\\n"),
+            '\t' => out.push_str("\\t"),
+            c if c.is_control() => { let _ = write!(out, "\\u{:04x}", c as u32); },
+            c => out.push(c)
+        }
+    }
+
+    out.push('"');
+    out
+}
+
+/// The answer as a JSON value; big integers become strings so no precision is lost.
+fn json_answer(answer: &Answer) -> String {
+    match answer {
+        Answer::Unsigned(v) => v.to_string(),
+        Answer::Signed(v) => v.to_string(),
+        Answer::Float(v) if v.is_finite() => format!("{:?}", v),
+        Answer::Float(_) => "null".to_string(),
+        Answer::Big(v) => json_string(&v.to_string()),
+        Answer::Text(v) => json_string(v)
+    }
+}
+
+fn elapsed_ms(elapsed: Duration) -> String {
+    format!("{:.3}", elapsed.as_secs_f64() * 1000.)
+}
+
+/// Writes `records` as a JSON array with one object per part.
+pub fn write_json(out: &mut impl Write, records: &[Record]) -> io::Result<()> {
+    writeln!(out, "[")?;
+
+    for (i, r) in records.iter().enumerate() {
+        let sep = if i + 1 < records.len() { "," } else { "" };
+
+        writeln!(
+            out,
+            r#"  {{"day": {}, "part": {}, "type": "{}", "answer": {}, "elapsed_ms": {}, "input_hash": "{:016x}"}}{}"#,
+            r.day, r.part.number(), r.answer.kind(), json_answer(&r.answer), elapsed_ms(r.elapsed), r.input_hash, sep
+        )?;
+    }
+
+    writeln!(out, "]")
+}
+
+/// Writes `records` as tab separated values under a header line.
+pub fn write_tsv(out: &mut impl Write, records: &[Record]) -> io::Result<()> {
+    writeln!(out, "day\tpart\ttype\tanswer\telapsed_ms\tinput_hash")?;
+
+    for r in records {
+        writeln!(
+            out,
+            "{}\t{}\t{}\t{}\t{}\t{:016x}",
+            r.day, r.part.number(), r.answer.kind(), r.answer, elapsed_ms(r.elapsed), r.input_hash
+        )?;
+    }
+
+    Ok(())
+}
+
+/// A duration in the most readable unit.
+fn human(elapsed: Duration) -> String {
+    if elapsed < Duration::from_millis(1) {
+        format!("{} µs", elapsed.as_micros())
+    } else if elapsed < Duration::from_secs(1) {
+        format!("{:.1} ms", elapsed.as_secs_f64() * 1000.)
+    } else {
+        format!("{:.2} s", elapsed.as_secs_f64())
+    }
+}
+
+/// Writes a table of the parts that were run, followed by the critical path:
+/// the slowest part, which the wall time of a parallel run cannot go below.
+pub fn write_table(out: &mut impl Write, done: &[Done], wall: Duration, threads: usize) -> io::Result<()> {
+    let rows: Vec<_> = done.iter()
+        .filter_map(|d| {
+            let (answer, status) = match &d.outcome {
+                Outcome::Solved(Some(answer)) => (answer.to_string(), "ok"),
+                Outcome::Solved(None) => { return None; },
+                Outcome::Failed(_) => ("-".to_string(), "parse error"),
+                Outcome::Panicked(_) => ("-".to_string(), "panicked")
+            };
+
+            Some((d, answer, status))
+        })
+        .collect();
+
+    let width = rows.iter().map(|(_, answer, _)| answer.chars().count()).max().unwrap_or(0).max("answer".len());
+
+    writeln!(out, "day  part  {:<width$}  {:>9}  status", "answer", "time", width = width)?;
+
+    for (d, answer, status) in &rows {
+        writeln!(
+            out,
+            "{:>3}  {:>4}  {:<width$}  {:>9}  {}",
+            d.day, d.part.number(), answer, human(d.elapsed), status, width = width
+        )?;
+    }
+
+    if let Some(slowest) = done.iter().max_by_key(|d| d.elapsed) {
+        let cpu: Duration = done.iter().map(|d| d.elapsed).sum();
+
+        writeln!(
+            out,
+            "\ncritical path: day {} part {}, {} of {} wall time ({} cpu time on {} thread{})",
+            slowest.day, slowest.part.number(), human(slowest.elapsed), human(wall), human(cpu), threads, if threads == 1 { "" } else { "s" }
+        )?;
+    }
+
+    Ok(())
+}
+
+#[test]
+fn json() {
+    let record = |day, answer| Record { day, part: Part::One, answer, elapsed: Duration::from_micros(1500), input_hash: 0xabc };
+    let records = [record(12, Answer::Float(1032.)), record(21, Answer::Text("a\"b".to_string()))];
+
+    let mut out = Vec::new();
+    write_json(&mut out, &records).unwrap();
+
+    assert_eq!(String::from_utf8(out).unwrap(), r#"[
+  {"day": 12, "part": 1, "type": "float", "answer": 1032.0, "elapsed_ms": 1.500, "input_hash": "0000000000000abc"},
+  {"day": 21, "part": 1, "type": "text", "answer": "a\"b", "elapsed_ms": 1.500, "input_hash": "0000000000000abc"}
+]
+"#);
+}
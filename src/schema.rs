@@ -0,0 +1,346 @@
+use std::fmt;
+use std::fs;
+use std::io;
+use std::ops::RangeInclusive;
+use std::path::{Path, PathBuf};
+
+use regex::Regex;
+use toml::Value;
+use toml::value::Table;
+
+#[derive(Debug)]
+pub enum SchemaError {
+    Io { path: PathBuf, err: io::Error },
+    Toml(toml::de::Error),
+    Invalid { key: String, reason: &'static str }
+}
+
+impl fmt::Display for SchemaError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            SchemaError::Io { path, err } => write!(f, "cannot read {}: {}", path.display(), err),
+            SchemaError::Toml(err) => write!(f, "malformed schema: {}", err),
+            SchemaError::Invalid { key, reason } => write!(f, "malformed schema at `{}`: {}", key, reason)
+        }
+    }
+}
+
+impl std::error::Error for SchemaError {}
+
+/// What the value of a field has to look like.
+#[derive(Clone, Debug)]
+pub enum FieldType {
+    Any,
+    /// An integer in the range.
+    Range(RangeInclusive<i64>),
+    /// An integer followed by a unit, in the range given for that unit.
+    Units(Vec<(String, RangeInclusive<i64>)>),
+    /// Text the regex matches.
+    Regex(Regex),
+    /// One of the values.
+    Enum(Vec<String>)
+}
+
+#[derive(Clone, Debug)]
+pub struct Field {
+    pub name: String,
+    pub kind: FieldType,
+    pub required: bool,
+    /// Shown when the value is invalid.
+    pub message: String
+}
+
+/// Why a field of a record is not valid.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum Problem {
+    Missing,
+    /// The field is given more than once.
+    Duplicate,
+    /// Not a number, or no number before the unit.
+    Unparsable,
+    OutOfRange,
+    /// A unit the field does not have a range for, or none at all.
+    BadUnit,
+    /// The regex does not match.
+    Mismatch,
+    /// Not one of the values of an enum.
+    NotAllowed
+}
+
+impl Problem {
+    /// A short name, like `out_of_range`.
+    pub fn name(self) -> &'static str {
+        match self {
+            Problem::Missing => "missing",
+            Problem::Duplicate => "duplicate",
+            Problem::Unparsable => "unparsable",
+            Problem::OutOfRange => "out_of_range",
+            Problem::BadUnit => "bad_unit",
+            Problem::Mismatch => "mismatch",
+            Problem::NotAllowed => "not_allowed"
+        }
+    }
+}
+
+impl fmt::Display for Problem {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            Problem::Missing => "missing",
+            Problem::Duplicate => "given more than once",
+            Problem::Unparsable => "not a number",
+            Problem::OutOfRange => "out of range",
+            Problem::BadUnit => "unknown unit",
+            Problem::Mismatch => "does not match the pattern",
+            Problem::NotAllowed => "not an allowed value"
+        })
+    }
+}
+
+impl Field {
+    /// What is wrong with a value, if anything.
+    pub fn problem(&self, value: &str) -> Option<Problem> {
+        let within = |range: &RangeInclusive<i64>, v| if range.contains(&v) { None } else { Some(Problem::OutOfRange) };
+
+        match &self.kind {
+            FieldType::Any => None,
+            FieldType::Range(range) => match value.parse() {
+                Ok(v) => within(range, v),
+                Err(_) => Some(Problem::Unparsable)
+            },
+            FieldType::Units(units) => {
+                let pos = value.find(|c: char| !c.is_ascii_digit() && c != '-').unwrap_or(value.len());
+
+                match (value[..pos].parse(), units.iter().find(|(unit, _)| *unit == value[pos..])) {
+                    (Err(_), _) => Some(Problem::Unparsable),
+                    (_, None) => Some(Problem::BadUnit),
+                    (Ok(v), Some((_, range))) => within(range, v)
+                }
+            },
+            FieldType::Regex(re) if re.is_match(value) => None,
+            FieldType::Regex(_) => Some(Problem::Mismatch),
+            FieldType::Enum(values) if values.iter().any(|v| v == value) => None,
+            FieldType::Enum(_) => Some(Problem::NotAllowed)
+        }
+    }
+
+    /// Checks a value, `None` if it cannot be parsed at all.
+    pub fn check(&self, value: &str) -> Option<bool> {
+        match self.problem(value) {
+            None => Some(true),
+            Some(Problem::Unparsable) | Some(Problem::BadUnit) => None,
+            Some(_) => Some(false)
+        }
+    }
+}
+
+/// A problem with one field of a record.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Issue {
+    pub field: String,
+    /// `None` if the field is missing.
+    pub value: Option<String>,
+    pub problem: Problem,
+    /// The message the schema has for the field.
+    pub message: String
+}
+
+impl fmt::Display for Issue {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match (&self.value, self.problem) {
+            (None, problem) => write!(f, "{}: {}", self.field, problem),
+            (Some(value), Problem::Duplicate) => write!(f, "{}: {}, `{}`", self.field, Problem::Duplicate, value),
+            (Some(value), problem) => write!(f, "{}: {}, `{}`: {}", self.field, problem, value, self.message)
+        }
+    }
+}
+
+/// Fields of `name:value` records, one `[fields.<name>]` table each.
+///
+/// ```toml
+/// [fields.hgt]
+/// type = "units"             # or "range", "regex", "enum", "any" (the default)
+/// units = { cm = [150, 193], in = [59, 76] }
+/// required = true            # the default
+/// message = "height must be 150 to 193cm or 59 to 76in"
+/// ```
+///
+/// Ranges take `min` and `max`, regexes a `pattern` and enums `values`.
+/// Fields the schema does not mention are allowed and take any value.
+/// Fields are kept in order of name, whatever the order in the file.
+#[derive(Clone, Debug, Default)]
+pub struct Schema {
+    pub fields: Vec<Field>
+}
+
+fn integer(value: &Value, key: &str) -> Result<i64, SchemaError> {
+    value.as_integer().ok_or_else(|| SchemaError::Invalid { key: key.to_string(), reason: "expected an integer" })
+}
+
+fn field_type(table: &Table, key: &str) -> Result<FieldType, SchemaError> {
+    let invalid = |sub: &str, reason| SchemaError::Invalid { key: format!("{}.{}", key, sub), reason };
+    let get = |sub: &str| table.get(sub).ok_or_else(|| invalid(sub, "missing"));
+
+    let kind = match table.get("type") {
+        Some(kind) => kind.as_str().ok_or_else(|| invalid("type", "expected a string"))?,
+        None => "any"
+    };
+
+    Ok(match kind {
+        "any" => FieldType::Any,
+        "range" => {
+            let min = integer(get("min")?, &format!("{}.min", key))?;
+            let max = integer(get("max")?, &format!("{}.max", key))?;
+            FieldType::Range(min..=max)
+        },
+        "units" => {
+            let units = get("units")?.as_table().ok_or_else(|| invalid("units", "expected a table"))?;
+            let ranges = units.iter().map(|(unit, range)| {
+                let key = format!("{}.units.{}", key, unit);
+
+                match range.as_array().map(Vec::as_slice) {
+                    Some([min, max]) => Ok((unit.clone(), integer(min, &key)?..=integer(max, &key)?)),
+                    _ => Err(SchemaError::Invalid { key, reason: "expected [min, max]" })
+                }
+            });
+
+            FieldType::Units(ranges.collect::<Result<_, _>>()?)
+        },
+        "regex" => {
+            let pattern = get("pattern")?.as_str().ok_or_else(|| invalid("pattern", "expected a string"))?;
+            FieldType::Regex(Regex::new(pattern).map_err(|_| invalid("pattern", "expected a valid regex"))?)
+        },
+        "enum" => {
+            let values = get("values")?.as_array().ok_or_else(|| invalid("values", "expected an array"))?;
+            let values = values.iter().map(|v| v.as_str().map(str::to_string).ok_or_else(|| invalid("values", "expected strings")));
+            FieldType::Enum(values.collect::<Result<_, _>>()?)
+        },
+        _ => { return Err(invalid("type", "expected any, range, units, regex or enum")); }
+    })
+}
+
+impl Schema {
+    pub fn parse(text: &str) -> Result<Self, SchemaError> {
+        let root = text.parse::<Value>().map_err(SchemaError::Toml)?;
+        let invalid = |key: &str, reason| SchemaError::Invalid { key: key.to_string(), reason };
+
+        let fields = match root.get("fields") {
+            Some(fields) => fields.as_table().ok_or_else(|| invalid("fields", "expected a table"))?,
+            None => { return Ok(Schema::default()); }
+        };
+
+        let fields = fields.iter().map(|(name, table)| {
+            let key = format!("fields.{}", name);
+            let table = table.as_table().ok_or_else(|| invalid(&key, "expected a table"))?;
+
+            let required = match table.get("required") {
+                Some(required) => required.as_bool().ok_or_else(|| invalid(&format!("{}.required", key), "expected a boolean"))?,
+                None => true
+            };
+
+            let message = match table.get("message") {
+                Some(message) => message.as_str().ok_or_else(|| invalid(&format!("{}.message", key), "expected a string"))?.to_string(),
+                None => format!("invalid {}", name)
+            };
+
+            Ok(Field { name: name.clone(), kind: field_type(table, &key)?, required, message })
+        });
+
+        Ok(Schema { fields: fields.collect::<Result<_, _>>()? })
+    }
+
+    pub fn load(path: &Path) -> Result<Self, SchemaError> {
+        let text = fs::read_to_string(path).map_err(|err| SchemaError::Io { path: path.to_path_buf(), err })?;
+        Schema::parse(&text)
+    }
+
+    pub fn field(&self, name: &str) -> Option<&Field> {
+        self.fields.iter().find(|f| f.name == name)
+    }
+
+    /// Checks a value of the named field, `None` if it cannot be parsed at all.
+    pub fn check(&self, name: &str, value: &str) -> Option<bool> {
+        self.field(name).map_or(Some(true), |field| field.check(value))
+    }
+
+    /// Whether `record` has every required field.
+    pub fn complete(&self, record: &[(String, String)]) -> bool {
+        self.fields.iter()
+            .filter(|field| field.required)
+            .all(|field| record.iter().any(|(name, _)| *name == field.name))
+    }
+
+    /// Whether `record` has every required field and only valid values.
+    pub fn accepts(&self, record: &[(String, String)]) -> bool {
+        self.complete(record) && record.iter().all(|(name, value)| self.check(name, value).unwrap_or(false))
+    }
+
+    /// Everything wrong with `record`: missing required fields by name, then
+    /// repeated and invalid fields in record order, values only checked if
+    /// `values` is set.
+    ///
+    /// A field given more than once is reported, but does not make
+    /// [`Schema::accepts`] fail on its own.
+    pub fn diagnose(&self, record: &[(String, String)], values: bool) -> Vec<Issue> {
+        let mut issues: Vec<Issue> = self.fields.iter()
+            .filter(|field| field.required && record.iter().all(|(name, _)| *name != field.name))
+            .map(|field| Issue { field: field.name.clone(), value: None, problem: Problem::Missing, message: field.message.clone() })
+            .collect();
+
+        for (i, (name, value)) in record.iter().enumerate() {
+            let field = self.field(name);
+            let message = field.map_or_else(String::new, |f| f.message.clone());
+            let issue = |problem| Issue { field: name.clone(), value: Some(value.clone()), problem, message: message.clone() };
+
+            if record[..i].iter().any(|(earlier, _)| earlier == name) {
+                issues.push(issue(Problem::Duplicate));
+            }
+
+            if let Some(problem) = field.filter(|_| values).and_then(|f| f.problem(value)) {
+                issues.push(issue(problem));
+            }
+        }
+
+        issues
+    }
+}
+
+#[test]
+fn schema() {
+    let schema = Schema::parse(r#"
+        [fields.age]
+        type = "range"
+        min = 0
+        max = 150
+        message = "age must be from 0 to 150"
+
+        [fields.len]
+        type = "units"
+        units = { m = [0, 10], km = [0, 1] }
+
+        [fields.note]
+        required = false
+    "#).expect("valid schema");
+
+    let record = |fields: &[(&str, &str)]| fields.iter().map(|&(n, v)| (n.to_string(), v.to_string())).collect::<Vec<_>>();
+
+    assert_eq!(schema.check("len", "3km"), Some(false));
+    assert_eq!(schema.check("len", "3ft"), None);
+    assert_eq!(schema.check("age", "-1"), Some(false));
+    assert_eq!(schema.check("other", "anything"), Some(true));
+
+    assert!(schema.accepts(&record(&[("age", "30"), ("len", "7m")])));
+    assert!(!schema.complete(&record(&[("age", "30"), ("note", "hi")])));
+
+    let issues = schema.diagnose(&record(&[("age", "200"), ("note", "hi"), ("len", "3ft"), ("note", "again")]), true);
+    let problems: Vec<_> = issues.iter().map(|i| (i.field.as_str(), i.problem)).collect();
+    assert_eq!(problems, [("age", Problem::OutOfRange), ("len", Problem::BadUnit), ("note", Problem::Duplicate)]);
+    assert_eq!(issues[0].to_string(), "age: out of range, `200`: age must be from 0 to 150");
+
+    let issues = schema.diagnose(&record(&[("age", "x"), ("note", "hi")]), true);
+    assert_eq!(issues.iter().map(Issue::to_string).collect::<Vec<_>>(), ["len: missing", "age: not a number, `x`: age must be from 0 to 150"]);
+    assert_eq!(schema.diagnose(&record(&[("age", "x")]), false).len(), 1);
+
+    let err = Schema::parse("[fields.x]\ntype = \"range\"\nmin = 1").unwrap_err();
+    assert_eq!(err.to_string(), "malformed schema at `fields.x.max`: missing");
+    assert!(matches!(Schema::parse("[fields.x]\ntype = \"regex\"\npattern = \"(\""), Err(SchemaError::Invalid { .. })));
+}
@@ -0,0 +1,142 @@
+use std::fmt;
+
+use num::bigint::{BigInt, BigUint};
+
+use crate::Part;
+use crate::error::ParseError;
+
+/// A puzzle answer, keeping the type the solver produced it with.
+#[derive(Clone, Debug, PartialEq)]
+pub enum Answer {
+    Unsigned(u64),
+    Signed(i64),
+    Big(BigInt),
+    Float(f64),
+    Text(String)
+}
+
+impl fmt::Display for Answer {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Answer::Unsigned(v) => write!(f, "{}", v),
+            Answer::Signed(v) => write!(f, "{}", v),
+            Answer::Big(v) => write!(f, "{}", v),
+            Answer::Float(v) => write!(f, "{:.2}", v),
+            Answer::Text(v) => write!(f, "{}", v)
+        }
+    }
+}
+
+impl Answer {
+    /// Name of the variant, as used by the structured output formats.
+    pub fn kind(&self) -> &'static str {
+        match self {
+            Answer::Unsigned(_) => "unsigned",
+            Answer::Signed(_) => "signed",
+            Answer::Big(_) => "big",
+            Answer::Float(_) => "float",
+            Answer::Text(_) => "text"
+        }
+    }
+}
+
+macro_rules! answer_from {
+    ($variant:ident, $target:ty, $($source:ty),+) => {
+        $(
+            impl From<$source> for Answer {
+                fn from(v: $source) -> Self {
+                    Answer::$variant(v as $target)
+                }
+            }
+        )+
+    }
+}
+
+answer_from!(Unsigned, u64, u32, u64, usize);
+answer_from!(Signed, i64, i32, i64, isize);
+answer_from!(Float, f64, f64);
+
+impl From<BigInt> for Answer {
+    fn from(v: BigInt) -> Self {
+        Answer::Big(v)
+    }
+}
+
+impl From<BigUint> for Answer {
+    fn from(v: BigUint) -> Self {
+        Answer::Big(v.into())
+    }
+}
+
+impl From<String> for Answer {
+    fn from(v: String) -> Self {
+        Answer::Text(v)
+    }
+}
+
+/// Conversion of a part's result into an [`Answer`].
+///
+/// Everything convertible into `Answer` is an answer; `()` stands for a part
+/// that has no puzzle, like the second half of day 25.
+pub trait IntoAnswer {
+    fn into_answer(self) -> Option<Answer>;
+}
+
+impl<T: Into<Answer>> IntoAnswer for T {
+    fn into_answer(self) -> Option<Answer> {
+        Some(self.into())
+    }
+}
+
+impl IntoAnswer for () {
+    fn into_answer(self) -> Option<Answer> {
+        None
+    }
+}
+
+/// A single day's puzzle.
+///
+/// The raw input is parsed once into `Input`, which both parts then borrow.
+/// Parsing fails with a [`ParseError`] on malformed input; the parts assume a
+/// well-formed puzzle and panic if it turns out to have no solution.
+///
+/// ```
+/// use aoc2020::solver::Solver;
+/// use aoc2020::days::day1::Day1;
+///
+/// let entries = Day1::parse("1721\n979\n366\n299\n675\n1456").unwrap();
+/// assert_eq!(Day1::part_one(&entries), 514579);
+/// ```
+pub trait Solver {
+    /// Day of the calendar, 1 to 25.
+    const DAY: usize;
+
+    /// Parsed puzzle input shared by both parts.
+    type Input;
+
+    type One: IntoAnswer;
+    type Two: IntoAnswer;
+
+    fn parse(input: &str) -> Result<Self::Input, ParseError>;
+    fn part_one(input: &Self::Input) -> Self::One;
+    fn part_two(input: &Self::Input) -> Self::Two;
+}
+
+/// Parses `input` for `S`, locating any error within it.
+pub fn parse<S: Solver>(input: &str) -> Result<S::Input, ParseError> {
+    S::parse(input).map_err(|err| err.locate(S::DAY, input))
+}
+
+/// Parses `input` and solves one part of it, erasing the solver's types.
+///
+/// Returns `Ok(None)` if the day has no such part.
+pub fn run<S: Solver>(part: Part, input: &str) -> Result<Option<Answer>, ParseError> {
+    let input = parse::<S>(input)?;
+
+    let answer = match part {
+        Part::One => S::part_one(&input).into_answer(),
+        Part::Two => S::part_two(&input).into_answer()
+    };
+
+    Ok(answer)
+}
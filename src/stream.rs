@@ -0,0 +1,83 @@
+use std::fmt;
+use std::io::{self, BufRead};
+
+use crate::error::ParseError;
+
+/// Input read line by line could not be read or parsed.
+#[derive(Debug)]
+pub enum StreamError {
+    Io(io::Error),
+    Parse(ParseError)
+}
+
+impl fmt::Display for StreamError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            StreamError::Io(err) => write!(f, "cannot read input: {}", err),
+            StreamError::Parse(err) => err.fmt(f)
+        }
+    }
+}
+
+impl std::error::Error for StreamError {}
+
+impl From<io::Error> for StreamError {
+    fn from(err: io::Error) -> Self {
+        StreamError::Io(err)
+    }
+}
+
+impl From<ParseError> for StreamError {
+    fn from(err: ParseError) -> Self {
+        StreamError::Parse(err)
+    }
+}
+
+/// Iterator over the parsed lines of a reader, see [`parse_lines`].
+pub struct Lines<R, F> {
+    day: usize,
+    reader: R,
+    parse: F,
+    buf: String,
+    number: usize
+}
+
+impl<R: BufRead, T, F: FnMut(&str) -> Result<T, ParseError>> Iterator for Lines<R, F> {
+    type Item = Result<T, StreamError>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.buf.clear();
+
+        match self.reader.read_line(&mut self.buf) {
+            Ok(0) => None,
+            Ok(_) => {
+                self.number += 1;
+
+                let (day, number, buf) = (self.day, self.number, &self.buf);
+                Some((self.parse)(buf.trim()).map_err(|err| err.locate_line(day, number, buf).into()))
+            },
+            Err(err) => Some(Err(err.into()))
+        }
+    }
+}
+
+/// Parses each trimmed line of `reader` with `parse`, holding one line at a
+/// time. Errors are located at their line as if the whole input was parsed.
+pub fn parse_lines<R, T, F>(day: usize, reader: R, parse: F) -> Lines<R, F>
+    where R: BufRead, F: FnMut(&str) -> Result<T, ParseError>
+{
+    Lines { day, reader, parse, buf: String::new(), number: 0 }
+}
+
+#[test]
+fn located() {
+    let input = "1\n2\nx\n";
+    let err = parse_lines(9, input.as_bytes(), |l| crate::error::parse_as::<u32>(l, "a number"))
+        .collect::<Result<Vec<_>, _>>()
+        .unwrap_err();
+
+    match err {
+        StreamError::Parse(err) => assert_eq!(err.to_string(), r#"day 9, line 3, column 1: expected a number, found "x""#),
+        err => panic!("unexpected {}", err)
+    }
+}
@@ -0,0 +1,347 @@
+use std::collections::HashSet;
+use std::fmt;
+
+use smallvec::SmallVec;
+
+use crate::error::{ParseError, parse_as};
+
+/// Registers `a` to `z`, `a` doubling as the accumulator.
+pub const REGISTERS: usize = 26;
+
+/// What an operand of an instruction may be.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum Kind {
+    /// A register, written as its letter.
+    Register,
+    /// A register or a signed number.
+    Value
+}
+
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+pub enum Operand {
+    Register(usize),
+    Immediate(isize)
+}
+
+/// Where to go after an instruction.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum Flow {
+    Next,
+    /// Relative to the instruction.
+    Jump(isize),
+    Halt
+}
+
+/// Runs an instruction, `None` if its arithmetic overflows.
+pub type Exec = fn(&mut Machine, &[Operand]) -> Option<Flow>;
+
+/// An instruction of an [`InstructionSet`]: its mnemonic, its operands and
+/// what it does, its operands being of the kinds given.
+#[derive(Clone, Copy)]
+pub struct Op {
+    pub name: &'static str,
+    pub operands: &'static [Kind],
+    pub exec: Exec
+}
+
+impl PartialEq for Op {
+    fn eq(&self, other: &Op) -> bool {
+        self.name == other.name
+    }
+}
+
+impl Eq for Op {}
+
+impl fmt::Debug for Op {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.name)
+    }
+}
+
+/// An instruction with its operands.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Insn {
+    pub op: Op,
+    pub args: SmallVec<[Operand; 2]>
+}
+
+pub type Program = Vec<Insn>;
+
+const fn op(name: &'static str, operands: &'static [Kind], exec: Exec) -> Op {
+    Op { name, operands, exec }
+}
+
+const NOP: Op = op("nop", &[Kind::Value], |_, _| Some(Flow::Next));
+const ACC: Op = op("acc", &[Kind::Value], |m, args| { m.registers[0] = m.registers[0].checked_add(m.value(args[0]))?; Some(Flow::Next) });
+const JMP: Op = op("jmp", &[Kind::Value], |m, args| Some(Flow::Jump(m.value(args[0]))));
+
+const HLT: Op = op("hlt", &[], |_, _| Some(Flow::Halt));
+const MOV: Op = op("mov", &[Kind::Register, Kind::Value], |m, args| { *m.register_mut(args[0]) = m.value(args[1]); Some(Flow::Next) });
+const ADD: Op = op("add", &[Kind::Register, Kind::Value], |m, args| { let sum = m.value(args[0]).checked_add(m.value(args[1]))?; *m.register_mut(args[0]) = sum; Some(Flow::Next) });
+const MUL: Op = op("mul", &[Kind::Register, Kind::Value], |m, args| { let product = m.value(args[0]).checked_mul(m.value(args[1]))?; *m.register_mut(args[0]) = product; Some(Flow::Next) });
+const JZ: Op = op("jz", &[Kind::Value, Kind::Value], |m, args| Some(if m.value(args[0]) == 0 { Flow::Jump(m.value(args[1])) } else { Flow::Next }));
+const JNZ: Op = op("jnz", &[Kind::Value, Kind::Value], |m, args| Some(if m.value(args[0]) != 0 { Flow::Jump(m.value(args[1])) } else { Flow::Next }));
+
+/// The instructions a program may use, looked up by mnemonic.
+#[derive(Clone, Debug, Default)]
+pub struct InstructionSet {
+    ops: Vec<Op>
+}
+
+impl InstructionSet {
+    /// The boot code of day 8: `nop`, `acc` and `jmp`.
+    pub fn handheld() -> Self {
+        InstructionSet { ops: vec![NOP, ACC, JMP] }
+    }
+
+    /// The handheld set with `hlt`, `mov`, `add` and `mul` on registers, and
+    /// `jz` and `jnz` jumping by their second operand if the first is zero
+    /// or not.
+    pub fn extended() -> Self {
+        let mut set = InstructionSet::handheld();
+        set.ops.extend_from_slice(&[HLT, MOV, ADD, MUL, JZ, JNZ]);
+        set
+    }
+
+    /// Adds `op`, replacing an instruction of the same name.
+    pub fn add(&mut self, op: Op) {
+        self.ops.retain(|o| o.name != op.name);
+        self.ops.push(op);
+    }
+
+    pub fn get(&self, name: &str) -> Option<Op> {
+        self.ops.iter().find(|op| op.name == name).copied()
+    }
+
+    fn operand(text: &str, kind: Kind) -> Result<Operand, ParseError> {
+        let mut chars = text.chars();
+
+        match (chars.next(), chars.next()) {
+            (Some(c @ 'a'..='z'), None) => Ok(Operand::Register((c as u8 - b'a') as usize)),
+            _ if kind == Kind::Register => Err(ParseError::new(text, "a register from `a` to `z`")),
+            _ => Ok(Operand::Immediate(parse_as(text, "a register or a signed number")?))
+        }
+    }
+
+    /// Reads an instruction per line, its operands after it separated by spaces.
+    pub fn parse(&self, input: &str) -> Result<Program, ParseError> {
+        input.lines()
+            .map(|line| {
+                let mut words = line.split_whitespace();
+                let name = words.next().ok_or_else(|| ParseError::new(line, "an instruction"))?;
+                let op = self.get(name).ok_or_else(|| {
+                    let names: Vec<_> = self.ops.iter().map(|op| format!("`{}`", op.name)).collect();
+                    ParseError::new(name, format!("one of {}", names.join(", ")))
+                })?;
+
+                let args = op.operands.iter()
+                    .map(|&kind| Self::operand(words.next().ok_or_else(|| ParseError::end(line, "another operand"))?, kind))
+                    .collect::<Result<_, _>>()?;
+
+                match words.next() {
+                    Some(extra) => Err(ParseError::new(extra, "the end of the line")),
+                    None => Ok(Insn { op, args })
+                }
+            })
+            .collect()
+    }
+}
+
+/// Instruction pointer and registers.
+#[derive(Clone, Debug, Eq, Hash, PartialEq)]
+pub struct Machine {
+    pub ip: usize,
+    pub registers: [isize; REGISTERS]
+}
+
+impl Machine {
+    pub fn new() -> Self {
+        Machine { ip: 0, registers: [0; REGISTERS] }
+    }
+
+    pub fn value(&self, operand: Operand) -> isize {
+        match operand {
+            Operand::Register(r) => self.registers[r],
+            Operand::Immediate(v) => v
+        }
+    }
+
+    /// Panics on an immediate, which parsing rules out for register operands.
+    pub fn register_mut(&mut self, operand: Operand) -> &mut isize {
+        match operand {
+            Operand::Register(r) => &mut self.registers[r],
+            Operand::Immediate(_) => panic!("an immediate is not a register")
+        }
+    }
+
+    /// The register with the given letter.
+    pub fn register(&self, name: char) -> isize {
+        self.registers[(name as u8 - b'a') as usize]
+    }
+}
+
+impl Default for Machine {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// When a program counts as stuck in a loop.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum LoopCheck {
+    /// An instruction is about to run a second time, right for programs
+    /// whose jumps never depend on registers.
+    Instruction,
+    /// The machine is about to be in the same state a second time.
+    State,
+    Never
+}
+
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub struct Limits {
+    /// How many instructions to run at most.
+    pub budget: Option<usize>,
+    pub loops: LoopCheck
+}
+
+impl Default for Limits {
+    fn default() -> Self {
+        Limits { budget: None, loops: LoopCheck::Instruction }
+    }
+}
+
+/// Why a program stopped without halting.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum Fault {
+    /// A jump to neither an instruction nor just past the last one.
+    OutOfBounds { ip: usize, target: isize },
+    InfiniteLoop { ip: usize },
+    OutOfBudget { budget: usize },
+    /// The instruction at `ip` computed a value, or a jump target, out of
+    /// the range of a register.
+    Overflow { ip: usize }
+}
+
+impl fmt::Display for Fault {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Fault::OutOfBounds { ip, target } => write!(f, "instruction {} jumps out of the program to {}", ip, target),
+            Fault::InfiniteLoop { ip } => write!(f, "infinite loop at instruction {}", ip),
+            Fault::OutOfBudget { budget } => write!(f, "no halt after {} instructions", budget),
+            Fault::Overflow { ip } => write!(f, "instruction {} overflows", ip)
+        }
+    }
+}
+
+impl std::error::Error for Fault {}
+
+/// How a run ended, and the machine at that point.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Run {
+    pub machine: Machine,
+    /// Instructions executed.
+    pub steps: usize,
+    pub result: Result<(), Fault>
+}
+
+impl Run {
+    /// Ran past the last instruction or into a `hlt`.
+    pub fn halted(&self) -> bool {
+        self.result.is_ok()
+    }
+
+    pub fn looped(&self) -> bool {
+        matches!(self.result, Err(Fault::InfiniteLoop { .. }))
+    }
+
+    /// Jumped out of bounds, overflowed or ran out of budget.
+    pub fn trapped(&self) -> bool {
+        !self.halted() && !self.looped()
+    }
+}
+
+/// Runs `program` on `machine` until it halts or `limits` stop it.
+pub fn run(program: &[Insn], mut machine: Machine, limits: Limits) -> Run {
+    let mut seen_ips = vec![false; program.len()];
+    let mut seen_states = HashSet::new();
+    let mut steps = 0;
+
+    let result = loop {
+        let insn = match program.get(machine.ip) {
+            Some(insn) => insn,
+            None => { break Ok(()); }
+        };
+
+        let repeated = match limits.loops {
+            LoopCheck::Instruction => std::mem::replace(&mut seen_ips[machine.ip], true),
+            LoopCheck::State => !seen_states.insert(machine.clone()),
+            LoopCheck::Never => false
+        };
+
+        if repeated {
+            break Err(Fault::InfiniteLoop { ip: machine.ip });
+        }
+
+        if let Some(budget) = limits.budget.filter(|&budget| steps >= budget) {
+            break Err(Fault::OutOfBudget { budget });
+        }
+
+        steps += 1;
+
+        match (insn.op.exec)(&mut machine, &insn.args) {
+            None => { break Err(Fault::Overflow { ip: machine.ip }); },
+            Some(Flow::Next) => { machine.ip += 1; },
+            Some(Flow::Halt) => { break Ok(()); },
+            Some(Flow::Jump(offset)) => {
+                let target = match (machine.ip as isize).checked_add(offset) {
+                    Some(target) => target,
+                    None => { break Err(Fault::Overflow { ip: machine.ip }); }
+                };
+
+                if target < 0 || target as usize > program.len() {
+                    break Err(Fault::OutOfBounds { ip: machine.ip, target });
+                }
+
+                machine.ip = target as usize;
+            }
+        }
+    };
+
+    Run { machine, steps, result }
+}
+
+#[test]
+fn machine() {
+    let set = InstructionSet::extended();
+    let limits = Limits { budget: Some(1000), loops: LoopCheck::State };
+
+    // b = 5!, counting a down from 5.
+    let factorial = set.parse("mov a 5\nmov b 1\nmul b a\nadd a -1\njnz a -2\nhlt\nmov b 0").unwrap();
+    let done = run(&factorial, Machine::new(), limits);
+    assert!(done.halted());
+    assert_eq!((done.machine.register('b'), done.machine.ip, done.steps), (120, 5, 18));
+
+    let spin = set.parse("add c 1\njz a -1").unwrap();
+    assert_eq!(run(&spin, Machine::new(), limits).result, Err(Fault::OutOfBudget { budget: 1000 }));
+    assert_eq!(run(&set.parse("jz a 0").unwrap(), Machine::new(), limits).result, Err(Fault::InfiniteLoop { ip: 0 }));
+
+    let huge = run(&set.parse("mov a 30\nmov b 1\nmul b a\nadd a -1\njnz a -2").unwrap(), Machine::new(), limits);
+    assert!(huge.trapped());
+    assert_eq!(huge.result, Err(Fault::Overflow { ip: 2 }));
+    assert_eq!(run(&set.parse("acc +9223372036854775807\nacc +1").unwrap(), Machine::new(), limits).result, Err(Fault::Overflow { ip: 1 }));
+    assert_eq!(run(&set.parse("nop +0\njmp +9223372036854775807").unwrap(), Machine::new(), limits).result, Err(Fault::Overflow { ip: 1 }));
+
+    let away = run(&set.parse("nop +0\njmp -2").unwrap(), Machine::new(), Limits::default());
+    assert!(away.trapped());
+    assert_eq!(away.result.unwrap_err().to_string(), "instruction 1 jumps out of the program to -1");
+
+    assert_eq!(set.parse("mov 1 a").unwrap_err().text, "1");
+    assert_eq!(set.parse("add a").unwrap_err().text, "");
+    assert_eq!(set.parse("hlt now").unwrap_err().text, "now");
+    assert!(InstructionSet::handheld().parse("hlt").is_err());
+
+    let mut custom = InstructionSet::handheld();
+    custom.add(op("dbl", &[Kind::Register], |m, args| { *m.register_mut(args[0]) *= 2; Some(Flow::Next) }));
+    let doubled = run(&custom.parse("acc +3\ndbl a").unwrap(), Machine::new(), Limits::default());
+    assert_eq!(doubled.machine.register('a'), 6);
+}